@@ -35,7 +35,8 @@ fn to_pixalated(v: vec2<f32>) -> vec2<f32> {
 
 @fragment
 fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
-    let origin = to_pixalated(vec2<f32>(640.0, 360.0));
+    // The effect spreads from the center of the screen, whatever its size.
+    let origin = to_pixalated(vec2<f32>(textureDimensions(screen_texture)) / 2.0);
     let bottom_left = to_pixalated(vec2<f32>(0.0, 0.0));
     let position = to_pixalated(in.position.xy);
 
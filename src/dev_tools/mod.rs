@@ -1,10 +1,12 @@
 //! Development tools for the game. This plugin is only enabled in dev builds.
 
+mod tuning_panel;
+
 use bevy::{
     dev_tools::states::log_transitions, input::common_conditions::input_just_pressed, prelude::*,
 };
 
-use crate::screens::Screen;
+use crate::{game::replay::PlayReplay, screens::Screen};
 
 pub(super) fn plugin(app: &mut App) {
     // Log `Screen` state transitions.
@@ -15,10 +17,21 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         toggle_debug_ui.run_if(input_just_pressed(TOGGLE_KEY)),
     );
+
+    // Play back the last recorded gameplay session.
+    app.add_systems(Update, play_replay.run_if(input_just_pressed(REPLAY_KEY)));
+
+    // Edit gameplay values in a panel.
+    app.add_plugins(tuning_panel::plugin);
 }
 
 const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
+const REPLAY_KEY: KeyCode = KeyCode::F5;
 
 fn toggle_debug_ui(mut options: ResMut<UiDebugOptions>) {
     options.toggle();
 }
+
+fn play_replay(mut commands: Commands) {
+    commands.trigger(PlayReplay);
+}
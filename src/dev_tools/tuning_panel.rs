@@ -0,0 +1,191 @@
+//! A panel for editing the [`Tuning`] resources while the game runs.
+//!
+//! Edited values are logged in the format of the tuning file, so they can be copied into it.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    game::{
+        arrows::ArrowTuning, environment::StoneTuning, movement::MovementTuning,
+        player::PlayerTuning,
+    },
+    localization::LocalizedText,
+    theme::{interaction::Activate, widget},
+    tuning::{Tuning, format_tuning, format_value},
+};
+
+const TOGGLE_PANEL_KEY: KeyCode = KeyCode::F6;
+/// How much a value changes per click, as a fraction of it.
+const STEP_FRACTION: f32 = 0.1;
+const MIN_STEP: f32 = 0.01;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            toggle_panel.run_if(input_just_pressed(TOGGLE_PANEL_KEY)),
+            update_values::<PlayerTuning>.run_if(resource_changed::<PlayerTuning>),
+            update_values::<MovementTuning>.run_if(resource_changed::<MovementTuning>),
+            update_values::<ArrowTuning>.run_if(resource_changed::<ArrowTuning>),
+            update_values::<StoneTuning>.run_if(resource_changed::<StoneTuning>),
+        ),
+    );
+}
+
+#[derive(Component)]
+struct TuningPanel;
+
+/// Text showing the value of a field of a tuning resource.
+#[derive(Component)]
+struct TuningValue {
+    section: &'static str,
+    field: usize,
+}
+
+fn toggle_panel(
+    mut commands: Commands,
+    panel: Query<Entity, With<TuningPanel>>,
+    player: Res<PlayerTuning>,
+    movement: Res<MovementTuning>,
+    arrow: Res<ArrowTuning>,
+    stone: Res<StoneTuning>,
+) {
+    if let Ok(panel) = panel.single() {
+        commands.entity(panel).despawn();
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Tuning Panel"),
+        TuningPanel,
+        Node {
+            position_type: PositionType::Absolute,
+            right: px(10),
+            top: px(10),
+            flex_direction: FlexDirection::Column,
+            // Sections which don't fit below each other continue in another column.
+            flex_wrap: FlexWrap::Wrap,
+            max_height: percent(95),
+            row_gap: px(4),
+            column_gap: px(20),
+            padding: UiRect::all(px(10)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        GlobalZIndex(10),
+        children![
+            section(&*player),
+            section(&*movement),
+            section(&*arrow),
+            section(&*stone)
+        ],
+    ));
+}
+
+/// A row for each field of the tuning resource.
+fn section<T: Tuning>(tuning: &T) -> impl Bundle {
+    let rows = (0..tuning.field_len())
+        .filter_map(|index| {
+            let name = tuning.name_at(index)?.to_string();
+            let value = format_value(tuning.field_at(index)?)?;
+            Some(row::<T>(name, index, value))
+        })
+        .collect::<Vec<_>>();
+
+    (
+        Name::new(T::SECTION),
+        Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: px(4),
+            ..default()
+        },
+        Children::spawn(SpawnIter(rows.into_iter())),
+    )
+}
+
+fn row<T: Tuning>(name: String, index: usize, value: String) -> impl Bundle {
+    let decrease_name = name.clone();
+    let increase_name = name.clone();
+
+    (
+        Name::new(format!("{}.{name}", T::SECTION)),
+        Node {
+            align_items: AlignItems::Center,
+            column_gap: px(8),
+            ..default()
+        },
+        children![
+            (
+                Text::new(format!("{}.{name}", T::SECTION)),
+                TextFont::from_font_size(18.0),
+                Node {
+                    width: px(300),
+                    ..default()
+                },
+            ),
+            widget::button_small(
+                LocalizedText::verbatim("-"),
+                move |_: On<Activate>, mut tuning: ResMut<T>| {
+                    step_value(&mut *tuning, &decrease_name, -1.0);
+                }
+            ),
+            (
+                TuningValue {
+                    section: T::SECTION,
+                    field: index,
+                },
+                Text::new(value),
+                TextFont::from_font_size(18.0),
+                Node {
+                    width: px(80),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+            ),
+            widget::button_small(
+                LocalizedText::verbatim("+"),
+                move |_: On<Activate>, mut tuning: ResMut<T>| {
+                    step_value(&mut *tuning, &increase_name, 1.0);
+                }
+            ),
+        ],
+    )
+}
+
+/// Changes the field by a step in the given direction, and logs the new values.
+fn step_value<T: Tuning>(tuning: &mut T, name: &str, direction: f32) {
+    let Some(field) = tuning.field_mut(name) else {
+        return;
+    };
+
+    if let Some(value) = field.try_downcast_mut::<f32>() {
+        *value += direction * (value.abs() * STEP_FRACTION).max(MIN_STEP);
+    } else if let Some(value) = field.try_downcast_mut::<u32>() {
+        let step = (*value / 10).max(1);
+        *value = if direction < 0.0 {
+            value.saturating_sub(step)
+        } else {
+            value.saturating_add(step)
+        };
+    } else if let Some(value) = field.try_downcast_mut::<usize>() {
+        let step = (*value / 10).max(1);
+        *value = if direction < 0.0 {
+            value.saturating_sub(step)
+        } else {
+            value.saturating_add(step)
+        };
+    }
+
+    info!("Tuning changed:\n{}", format_tuning(tuning));
+}
+
+fn update_values<T: Tuning>(tuning: Res<T>, texts: Query<(&TuningValue, &mut Text)>) {
+    for (value, mut text) in texts {
+        if value.section != T::SECTION {
+            continue;
+        }
+        if let Some(formatted) = tuning.field_at(value.field).and_then(format_value) {
+            text.0 = formatted;
+        }
+    }
+}
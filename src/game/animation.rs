@@ -23,7 +23,7 @@ impl SpriteAnimation {
     }
 }
 
-fn update_animations(time: Res<Time>, query: Query<(&mut SpriteAnimation, &mut Sprite)>) {
+pub fn update_animations(time: Res<Time>, query: Query<(&mut SpriteAnimation, &mut Sprite)>) {
     for (mut animation, mut sprite) in query {
         if animation.paused {
             continue;
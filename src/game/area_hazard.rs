@@ -0,0 +1,257 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    game::{
+        DestroyOnNewLevel, NewLevel,
+        collision::{GameLayer, PlayerEntered, PlayerExited, PlayerTrigger},
+        environment::ROAD_SIZE,
+        health::PlayerHit,
+        hit_reaction::HitReaction,
+        level_data::level_data,
+        player::Player,
+    },
+    screens::Screen,
+};
+
+const HAZARD_Z: f32 = 120.0;
+const BEAM_THICKNESS: f32 = 24.0;
+const CLOSING_WALL_HEIGHT: f32 = 120.0;
+const ACTIVE_COLOR: Color = Color::srgba(0.9, 0.1, 0.25, 0.85);
+const SAFE_COLOR: Color = Color::srgba(0.9, 0.1, 0.25, 0.2);
+
+pub fn plugin(app: &mut App) {
+    app.add_observer(spawn_area_hazards)
+        .add_observer(on_player_enters_hazard)
+        .add_observer(on_player_leaves_hazard)
+        .add_systems(
+            FixedUpdate,
+            (
+                tick_pulsing_zones,
+                move_closing_walls,
+                hit_player_on_active_contact,
+            )
+                .chain()
+                .in_set(PausableSystems),
+        )
+        .add_systems(Update, update_hazard_color.in_set(PausableSystems));
+}
+
+/// Where and what kind of area hazard is placed in a level.
+#[derive(Clone, Copy, Debug)]
+pub struct HazardPlacement {
+    pub position: Vec2,
+    pub kind: HazardKind,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum HazardKind {
+    /// A beam rotating around its center, always harmful.
+    SweepingBeam { length: f32, angular_speed: f32 },
+    /// A zone which alternates between safe and harmful.
+    PulsingZone {
+        size: Vec2,
+        safe_secs: f32,
+        active_secs: f32,
+    },
+    /// Two walls sliding from the road sides towards `position`, leaving a `gap`
+    /// between them. Harmful while closing.
+    ClosingWalls { gap: f32, period: f32 },
+}
+
+#[derive(Component, Default)]
+struct AreaHazard {
+    active: bool,
+    touching_player: bool,
+}
+
+#[derive(Component)]
+struct PulsingZone {
+    timer: Timer,
+    safe_secs: f32,
+    active_secs: f32,
+}
+
+#[derive(Component)]
+struct ClosingWall {
+    timer: Timer,
+    open_x: f32,
+    closed_x: f32,
+}
+
+fn spawn_area_hazards(
+    new_level: On<NewLevel>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for placement in level_data(new_level.0).hazards {
+        match placement.kind {
+            HazardKind::SweepingBeam {
+                length,
+                angular_speed,
+            } => {
+                commands.spawn((
+                    Name::new("sweeping beam"),
+                    area_hazard(
+                        Vec2::new(length, BEAM_THICKNESS),
+                        placement.position,
+                        true,
+                        &mut meshes,
+                        &mut materials,
+                    ),
+                    AngularVelocity(angular_speed),
+                ));
+            }
+            HazardKind::PulsingZone {
+                size,
+                safe_secs,
+                active_secs,
+            } => {
+                commands.spawn((
+                    Name::new("pulsing zone"),
+                    area_hazard(size, placement.position, false, &mut meshes, &mut materials),
+                    PulsingZone {
+                        timer: Timer::from_seconds(safe_secs, TimerMode::Once),
+                        safe_secs,
+                        active_secs,
+                    },
+                ));
+            }
+            HazardKind::ClosingWalls { gap, period } => {
+                let width = ROAD_SIZE.x / 2.0;
+                let travel = width - gap / 2.0;
+
+                for side in [-1.0, 1.0] {
+                    let closed_x = placement.position.x + side * (gap + width) / 2.0;
+                    let open_x = closed_x + side * travel;
+
+                    commands.spawn((
+                        Name::new("closing wall"),
+                        area_hazard(
+                            Vec2::new(width, CLOSING_WALL_HEIGHT),
+                            placement.position.with_x(open_x),
+                            true,
+                            &mut meshes,
+                            &mut materials,
+                        ),
+                        ClosingWall {
+                            timer: Timer::from_seconds(period, TimerMode::Repeating),
+                            open_x,
+                            closed_x,
+                        },
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn area_hazard(
+    size: Vec2,
+    position: Vec2,
+    active: bool,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+) -> impl Bundle {
+    let color = if active { ACTIVE_COLOR } else { SAFE_COLOR };
+
+    (
+        DestroyOnNewLevel,
+        DespawnOnExit(Screen::Gameplay),
+        AreaHazard {
+            active,
+            ..default()
+        },
+        Mesh2d(meshes.add(Rectangle::from_size(size))),
+        MeshMaterial2d(materials.add(color)),
+        Collider::rectangle(size.x, size.y),
+        PlayerTrigger,
+        CollisionLayers::new(GameLayer::Hazard, GameLayer::Player),
+        RigidBody::Kinematic,
+        Transform::from_translation(position.extend(HAZARD_Z)),
+    )
+}
+
+fn tick_pulsing_zones(time: Res<Time>, zones: Query<(&mut PulsingZone, &mut AreaHazard)>) {
+    for (mut zone, mut hazard) in zones {
+        zone.timer.tick(time.delta());
+        if !zone.timer.just_finished() {
+            continue;
+        }
+
+        hazard.active = !hazard.active;
+        let duration = if hazard.active {
+            zone.active_secs
+        } else {
+            zone.safe_secs
+        };
+        zone.timer.set_duration(Duration::from_secs_f32(duration));
+        zone.timer.reset();
+    }
+}
+
+fn move_closing_walls(
+    time: Res<Time>,
+    walls: Query<(&mut ClosingWall, &mut AreaHazard, &mut Transform)>,
+) {
+    for (mut wall, mut hazard, mut transform) in walls {
+        wall.timer.tick(time.delta());
+
+        // Close during the first half of the period, open during the second one.
+        let t = wall.timer.fraction();
+        let closure = 1.0 - (2.0 * t - 1.0).abs();
+
+        transform.translation.x = wall.open_x.lerp(wall.closed_x, closure);
+        hazard.active = t < 0.5;
+    }
+}
+
+fn update_hazard_color(
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    hazards: Query<(&AreaHazard, &MeshMaterial2d<ColorMaterial>), Changed<AreaHazard>>,
+) {
+    for (hazard, material) in hazards {
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.color = if hazard.active {
+                ACTIVE_COLOR
+            } else {
+                SAFE_COLOR
+            };
+        }
+    }
+}
+
+fn hit_player_on_active_contact(
+    mut commands: Commands,
+    player: Single<&HitReaction, With<Player>>,
+    hazards: Query<(&AreaHazard, &Transform)>,
+) {
+    if player.is_invulnerable() {
+        return;
+    }
+
+    if let Some((_, transform)) = hazards
+        .iter()
+        .find(|(hazard, _)| hazard.active && hazard.touching_player)
+    {
+        commands.trigger(PlayerHit {
+            source: transform.translation.truncate(),
+        });
+    }
+}
+
+fn on_player_enters_hazard(event: On<PlayerEntered>, mut hazards: Query<&mut AreaHazard>) {
+    if let Ok(mut hazard) = hazards.get_mut(event.entity) {
+        hazard.touching_player = true;
+    }
+}
+
+fn on_player_leaves_hazard(event: On<PlayerExited>, mut hazards: Query<&mut AreaHazard>) {
+    if let Ok(mut hazard) = hazards.get_mut(event.entity) {
+        hazard.touching_player = false;
+    }
+}
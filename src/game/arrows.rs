@@ -4,31 +4,59 @@ use rand::Rng;
 
 use crate::{
     PausableSystems,
-    audio::sound_effect_volume,
     game::{
-        AllAssets, DestroyOnNewLevel, LevelNumber, LevelRestart, NewLevel, RandomSource,
-        environment::ROAD_SIZE, guide::ChangeGuideText, player::Player,
+        AllAssets, DestroyOnNewLevel, LevelNumber, NewLevel, RandomSource,
+        collision::{GameLayer, PlayerEntered, PlayerTrigger},
+        environment::ROAD_SIZE,
+        guide::{ChangeGuideText, GuidePriority},
+        health::PlayerHit,
+        hit_reaction::HitReaction,
+        player::Player,
     },
     screens::Screen,
+    tuning::{Tuning, init_tuning},
 };
 
 const SPAWN_Y_START: f32 = ROAD_SIZE.y / 3.0;
 const SPAWN_Y_END: f32 = ROAD_SIZE.y - 400.0;
 const ARROW_Z: f32 = 150.0;
-const ARROW_SPEED: f32 = 400.0;
 
 pub fn plugin(app: &mut App) {
+    init_tuning::<ArrowTuning>(app);
+
     app.add_observer(spawn_arrow_spawner).add_systems(
-        Update,
+        FixedUpdate,
         (handle_arrow_spawning, destroy_out_of_map).in_set(PausableSystems),
     );
 }
 
+/// Tunable values of the arrows, see [`crate::tuning`].
+#[derive(Resource, Reflect, Clone, Debug)]
+#[reflect(Resource)]
+pub struct ArrowTuning {
+    pub speed: f32,
+    /// Chance of an arrow being spawned each tick.
+    pub spawn_chance: f32,
+}
+
+impl Default for ArrowTuning {
+    fn default() -> Self {
+        Self {
+            speed: 400.0,
+            spawn_chance: 0.05,
+        }
+    }
+}
+
+impl Tuning for ArrowTuning {
+    const SECTION: &'static str = "arrow";
+}
+
 #[derive(Component)]
 struct ArrowSpawner;
 
 #[derive(Component)]
-struct Arrow;
+pub struct Arrow;
 
 fn spawn_arrow_spawner(new_level: On<NewLevel>, mut commands: Commands) {
     if new_level.0 < 1 {
@@ -49,8 +77,7 @@ fn spawn_arrow_spawner(new_level: On<NewLevel>, mut commands: Commands) {
             DespawnOnExit(Screen::Gameplay),
             Collider::rectangle(ROAD_SIZE.x, SPAWN_Y_END - SPAWN_Y_START),
             RigidBody::Static,
-            Sensor,
-            CollisionEventsEnabled,
+            PlayerTrigger,
             Transform::from_xyz(0.0, ROAD_SIZE.y / 2.0, 0.0),
         ))
         .observe(on_player_in_arrow_area);
@@ -61,9 +88,13 @@ fn handle_arrow_spawning(
     mut commands: Commands,
     assets: Res<AllAssets>,
     level_number: Res<LevelNumber>,
+    tuning: Res<ArrowTuning>,
     mut random_source: ResMut<RandomSource>,
 ) {
-    if !random_source.0.random_bool(0.05) {
+    if !random_source
+        .0
+        .random_bool(f64::from(tuning.spawn_chance.clamp(0.0, 1.0)))
+    {
         return;
     }
 
@@ -87,10 +118,11 @@ fn handle_arrow_spawning(
             Arrow,
             Sprite::from_image(handle),
             collider,
-            Sensor,
+            PlayerTrigger,
+            // Also detected by the near miss sensor.
+            CollisionLayers::new(GameLayer::Hazard, [GameLayer::Player, GameLayer::Trigger]),
             RigidBody::Kinematic,
-            LinearVelocity(Vec2::NEG_X * ARROW_SPEED),
-            CollisionEventsEnabled,
+            LinearVelocity(Vec2::NEG_X * tuning.speed),
             Transform::from_xyz(
                 ROAD_SIZE.x + 50.0,
                 random_source.0.random_range(SPAWN_Y_START..=SPAWN_Y_END),
@@ -110,34 +142,36 @@ fn destroy_out_of_map(mut commands: Commands, arrows: Query<(Entity, &Transform)
 }
 
 fn on_player_hit(
-    event: On<CollisionStart>,
+    event: On<PlayerEntered>,
     mut commands: Commands,
-    assets: Res<AllAssets>,
-    player: Single<Entity, With<Player>>,
+    reaction: Single<&HitReaction, With<Player>>,
+    arrows: Query<&Transform, With<Arrow>>,
 ) {
-    if event.collider2 != *player {
+    if reaction.is_invulnerable() {
         return;
     }
 
-    commands.spawn(sound_effect_volume(assets.cat_hurt.clone(), 0.4));
-    commands.trigger(LevelRestart);
+    let Ok(transform) = arrows.get(event.entity) else {
+        return;
+    };
+
+    commands.trigger(PlayerHit {
+        source: transform.translation.truncate(),
+    });
+    commands.entity(event.entity).despawn();
 }
 
 fn on_player_in_arrow_area(
-    event: On<CollisionStart>,
+    event: On<PlayerEntered>,
     mut commands: Commands,
     level_number: Res<LevelNumber>,
-    player: Single<Entity, With<Player>>,
 ) {
-    if event.collider1 == *player && event.collider2 != *player {
-        return;
-    }
-    commands.entity(event.observer()).despawn();
+    commands.entity(event.entity).despawn();
 
-    let text = if level_number.0 >= 2 {
-        "Oh no, avoid the toasts."
+    let key = if level_number.0 >= 2 {
+        "guide.avoid_toasts"
     } else {
-        "Oh no, avoid the arrows."
+        "guide.avoid_arrows"
     };
-    commands.trigger(ChangeGuideText(String::from(text)));
+    commands.trigger(ChangeGuideText::new(key, GuidePriority::Warning));
 }
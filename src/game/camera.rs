@@ -0,0 +1,308 @@
+//! The camera following the player, its zoom, and camera shake.
+//!
+//! The camera eases towards a point slightly ahead of the player, only moving once the player
+//! leaves a small dead zone, and never shows anything past the edges of the road.
+//!
+//! Each level sets how much of it the camera shows, and [`ZoomZone`]s change it in parts of the
+//! level. The [`PlayerZoom`] setting is applied on top.
+//!
+//! Any system can shake the camera by triggering [`AddTrauma`]. The shake grows with the square
+//! of the trauma, which decays over time, and is turned off by [`ReducedMotion`].
+
+use avian2d::prelude::*;
+use bevy::{camera::CameraUpdateSystems, prelude::*};
+
+use crate::{
+    PausableSystems,
+    game::{
+        LevelCompleted, LevelNumber, NewLevel, environment::ROAD_SIZE, health::PlayerHit,
+        level_data::level_data, player::Player,
+    },
+    persistence,
+    screens::Screen,
+};
+
+const REDUCED_MOTION_SAVE_NAME: &str = "reduced_motion";
+const ZOOM_SAVE_NAME: &str = "zoom";
+
+/// How quickly the camera catches up with its target, higher is faster.
+const FOLLOW_DECAY_RATE: f32 = 6.0;
+/// How far ahead of the player the camera looks when moving at full speed.
+const LOOK_AHEAD_DISTANCE: f32 = 140.0;
+/// Speed of the player at which the look-ahead reaches its full distance.
+const LOOK_AHEAD_FULL_SPEED: f32 = 600.0;
+const LOOK_AHEAD_DECAY_RATE: f32 = 2.5;
+/// Half the size of the area in which the player moves without moving the camera.
+const DEAD_ZONE_HALF_SIZE: Vec2 = Vec2::new(40.0, 60.0);
+const ZOOM_DECAY_RATE: f32 = 1.5;
+
+const MAX_TRAUMA: f32 = 1.0;
+/// Trauma lost per second.
+const TRAUMA_DECAY: f32 = 1.2;
+const MAX_SHAKE_OFFSET: f32 = 28.0;
+/// Maximum shake rotation, in radians.
+const MAX_SHAKE_ANGLE: f32 = 0.04;
+const SHAKE_FREQUENCY: f32 = 25.0;
+
+const HIT_TRAUMA: f32 = 0.6;
+const LEVEL_COMPLETED_TRAUMA: f32 = 0.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<CameraFollow>()
+        .init_resource::<CameraShake>()
+        .insert_resource(ReducedMotion::load())
+        .insert_resource(PlayerZoom::load())
+        .add_systems(
+            PostUpdate,
+            (
+                // The view size of the new zoom is computed with the camera update.
+                zoom_camera.before(CameraUpdateSystems),
+                (follow_player, shake_camera)
+                    .chain()
+                    .after(CameraUpdateSystems)
+                    .before(TransformSystems::Propagate),
+            )
+                .run_if(in_state(Screen::Gameplay))
+                .in_set(PausableSystems),
+        )
+        .add_systems(OnExit(Screen::Gameplay), reset_camera)
+        .add_observer(snap_on_new_level)
+        .add_observer(add_trauma)
+        .add_observer(shake_on_hit)
+        .add_observer(shake_on_level_completed);
+}
+
+/// A part of a level where the camera shows more or less than in the rest of it.
+#[derive(Clone, Copy, Debug)]
+pub struct ZoomZone {
+    pub position: Vec2,
+    pub size: Vec2,
+    /// Scale of the camera projection while the player is in the zone.
+    pub view_scale: f32,
+}
+
+/// Zoom chosen by the player on top of the level's, where `2.0` shows everything twice as big.
+#[derive(Resource)]
+pub struct PlayerZoom(pub f32);
+
+impl Default for PlayerZoom {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl PlayerZoom {
+    const STEPS: [f32; 5] = [0.75, 0.9, 1.0, 1.25, 1.5];
+
+    /// Loads the saved zoom, if it is one of the steps.
+    fn load() -> Self {
+        persistence::load(ZOOM_SAVE_NAME)
+            .and_then(|saved| saved.trim().parse().ok())
+            .filter(|zoom| Self::STEPS.contains(zoom))
+            .map_or_else(Self::default, Self)
+    }
+
+    pub fn save(&self) {
+        persistence::save(ZOOM_SAVE_NAME, &self.0.to_string());
+    }
+
+    pub fn zoom_out(&mut self) {
+        if let Some(&step) = Self::STEPS.iter().rev().find(|&&step| step < self.0) {
+            self.0 = step;
+        }
+    }
+
+    pub fn zoom_in(&mut self) {
+        if let Some(&step) = Self::STEPS.iter().find(|&&step| step > self.0) {
+            self.0 = step;
+        }
+    }
+}
+
+/// Whether camera shake is turned off.
+#[derive(Resource, Default)]
+pub struct ReducedMotion(pub bool);
+
+impl ReducedMotion {
+    fn load() -> Self {
+        let saved = persistence::load(REDUCED_MOTION_SAVE_NAME).unwrap_or_default();
+        Self(saved.trim() == "on")
+    }
+
+    pub fn save(&self) {
+        persistence::save(REDUCED_MOTION_SAVE_NAME, if self.0 { "on" } else { "off" });
+    }
+}
+
+/// Shakes the camera, by an amount from `0.0` to `1.0` which adds up with other shakes.
+#[derive(Event)]
+pub struct AddTrauma(pub f32);
+
+#[derive(Resource, Default)]
+struct CameraFollow {
+    /// Where the camera is without shake.
+    position: Vec2,
+    /// Where the camera is heading, which stays still while the player is in the dead zone.
+    target: Vec2,
+    look_ahead: Vec2,
+    /// Whether the camera jumps to the player instead of easing, e.g. at the start of a level.
+    snap: bool,
+}
+
+#[derive(Resource, Default)]
+struct CameraShake {
+    trauma: f32,
+}
+
+fn snap_on_new_level(_: On<NewLevel>, mut follow: ResMut<CameraFollow>) {
+    follow.snap = true;
+}
+
+fn add_trauma(event: On<AddTrauma>, mut shake: ResMut<CameraShake>) {
+    shake.trauma = (shake.trauma + event.0).clamp(0.0, MAX_TRAUMA);
+}
+
+/// Removes the shake and zoom of gameplay from the camera, which is also used by the menus.
+fn reset_camera(
+    mut shake: ResMut<CameraShake>,
+    camera: Single<(&mut Transform, &mut Projection), With<Camera2d>>,
+) {
+    let (mut transform, mut projection) = camera.into_inner();
+    shake.trauma = 0.0;
+    transform.rotation = Quat::IDENTITY;
+    if let Projection::Orthographic(orthographic) = &mut *projection {
+        orthographic.scale = 1.0;
+    }
+}
+
+fn shake_on_hit(_: On<PlayerHit>, mut commands: Commands) {
+    commands.trigger(AddTrauma(HIT_TRAUMA));
+}
+
+fn shake_on_level_completed(_: On<LevelCompleted>, mut commands: Commands) {
+    commands.trigger(AddTrauma(LEVEL_COMPLETED_TRAUMA));
+}
+
+fn view_half_size(projection: &Projection) -> Vec2 {
+    match projection {
+        Projection::Orthographic(orthographic) => orthographic.area.half_size(),
+        _ => Vec2::ZERO,
+    }
+}
+
+fn zoom_camera(
+    time: Res<Time>,
+    follow: Res<CameraFollow>,
+    level: Res<LevelNumber>,
+    player_zoom: Res<PlayerZoom>,
+    player: Single<&Transform, With<Player>>,
+    mut projection: Single<&mut Projection, With<Camera2d>>,
+) {
+    let Projection::Orthographic(orthographic) = &**projection else {
+        return;
+    };
+
+    let position = player.translation.truncate();
+    let level = level_data(level.0);
+    let view_scale = level
+        .zoom_zones
+        .iter()
+        .find(|zone| Rect::from_center_size(zone.position, zone.size).contains(position))
+        .map_or(level.view_scale, |zone| zone.view_scale);
+    let target = view_scale / player_zoom.0;
+
+    let mut scale = orthographic.scale;
+    if follow.snap {
+        scale = target;
+    } else {
+        scale.smooth_nudge(&target, ZOOM_DECAY_RATE, time.delta_secs());
+    }
+
+    // Only touch the projection when the scale changes, so the camera isn't updated every frame.
+    if scale != orthographic.scale
+        && let Projection::Orthographic(orthographic) = &mut **projection
+    {
+        orthographic.scale = scale;
+    }
+}
+
+fn follow_player(
+    time: Res<Time>,
+    mut follow: ResMut<CameraFollow>,
+    player: Single<(&Transform, &LinearVelocity), (With<Player>, Without<Camera2d>)>,
+    projection: Single<&Projection, With<Camera2d>>,
+) {
+    let (player, velocity) = *player;
+    let delta = time.delta_secs();
+    let position = player.translation.truncate();
+    let view_half_size = view_half_size(&projection);
+
+    if follow.snap {
+        follow.snap = false;
+        follow.look_ahead = Vec2::ZERO;
+        follow.target = clamp_to_road(position, view_half_size);
+        follow.position = follow.target;
+        return;
+    }
+
+    let look_ahead = velocity.0.clamp_length_max(LOOK_AHEAD_FULL_SPEED) / LOOK_AHEAD_FULL_SPEED
+        * LOOK_AHEAD_DISTANCE;
+    follow
+        .look_ahead
+        .smooth_nudge(&look_ahead, LOOK_AHEAD_DECAY_RATE, delta);
+
+    // Move the target just enough to keep the focus point inside the dead zone.
+    let focus = position + follow.look_ahead;
+    let offset = focus - follow.target;
+    let target = follow.target + offset - offset.clamp(-DEAD_ZONE_HALF_SIZE, DEAD_ZONE_HALF_SIZE);
+    follow.target = clamp_to_road(target, view_half_size);
+
+    let mut position = follow.position;
+    position.smooth_nudge(&follow.target, FOLLOW_DECAY_RATE, delta);
+    follow.position = clamp_to_road(position, view_half_size);
+}
+
+fn shake_camera(
+    time: Res<Time>,
+    follow: Res<CameraFollow>,
+    mut shake: ResMut<CameraShake>,
+    reduced_motion: Res<ReducedMotion>,
+    camera: Single<(&mut Transform, &Projection), (With<Camera2d>, Without<Player>)>,
+) {
+    let (mut camera, projection) = camera.into_inner();
+    shake.trauma = (shake.trauma - TRAUMA_DECAY * time.delta_secs()).max(0.0);
+
+    let strength = if reduced_motion.0 {
+        0.0
+    } else {
+        shake.trauma * shake.trauma
+    };
+
+    // Sums of sines at unrelated frequencies move smoothly but look random, without using the
+    // random source of the simulation.
+    let t = time.elapsed_secs() * SHAKE_FREQUENCY;
+    let noise = |seed: f32| ((t + seed).sin() + (2.3 * t + 1.7 * seed).sin() * 0.5) / 1.5;
+    let offset = Vec2::new(noise(0.0), noise(11.0)) * MAX_SHAKE_OFFSET * strength;
+    let angle = noise(23.0) * MAX_SHAKE_ANGLE * strength;
+
+    let position = clamp_to_road(follow.position + offset, view_half_size(projection));
+    camera.translation = position.extend(camera.translation.z);
+    camera.rotation = Quat::from_rotation_z(angle);
+}
+
+/// Clamps the center of a view so that the view stays on the road. A view wider or taller than
+/// the road is centered on it.
+fn clamp_to_road(center: Vec2, view_half_size: Vec2) -> Vec2 {
+    let road = Rect::from_corners(
+        Vec2::new(-ROAD_SIZE.x / 2.0, 0.0),
+        Vec2::new(ROAD_SIZE.x / 2.0, ROAD_SIZE.y),
+    );
+    let min = road.min + view_half_size;
+    let max = road.max - view_half_size;
+
+    Vec2::select(
+        min.cmple(max),
+        center.clamp(min.min(max), max.max(min)),
+        road.center(),
+    )
+}
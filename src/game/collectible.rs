@@ -0,0 +1,179 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    audio::sound_effect_complex,
+    game::{
+        AllAssets, DestroyOnNewLevel, LevelCompleted, LevelNumber, NewLevel, WAKE_UP_LEVEL,
+        collision::{PlayerEntered, PlayerTrigger},
+        level_data::level_data,
+    },
+    localization::Localizer,
+    persistence,
+    screens::Screen,
+};
+
+const COLLECTIBLE_Z: f32 = 50.0;
+const COLLECTIBLE_RADIUS: f32 = 18.0;
+const COLLECTIBLE_COLOR: Color = Color::srgb(1.0, 0.6, 0.2);
+const SAVE_NAME: &str = "collectibles";
+
+pub fn plugin(app: &mut App) {
+    app.insert_resource(CollectibleProgress::load())
+        .add_observer(spawn_collectibles)
+        .add_observer(record_reached_level)
+        .add_observer(record_best_on_level_completed)
+        .add_systems(OnEnter(Screen::Gameplay), spawn_collectible_hud)
+        .add_systems(
+            Update,
+            update_collectible_hud.run_if(in_state(Screen::Gameplay)),
+        );
+}
+
+/// Collectibles picked up in the current attempt and the best completed attempt of each level,
+/// together with the furthest level the player has reached.
+#[derive(Resource)]
+pub struct CollectibleProgress {
+    pub collected: usize,
+    pub best: [usize; WAKE_UP_LEVEL],
+    /// Levels up to this one can be started from the level select.
+    pub reached: usize,
+}
+
+impl CollectibleProgress {
+    fn load() -> Self {
+        let mut best = [0; WAKE_UP_LEVEL];
+        let saved = persistence::load(SAVE_NAME).unwrap_or_default();
+        let mut lines = saved.lines();
+
+        // One line per level, followed by the furthest level reached.
+        for (best, line) in best.iter_mut().zip(lines.by_ref()) {
+            *best = line.trim().parse().unwrap_or_default();
+        }
+        let reached = lines
+            .next()
+            .and_then(|line| line.trim().parse().ok())
+            .unwrap_or_default();
+
+        Self {
+            collected: 0,
+            best,
+            reached,
+        }
+    }
+
+    fn save(&self) {
+        let mut contents = self.best.map(|best| best.to_string()).join("\n");
+        contents += &format!("\n{}", self.reached);
+        persistence::save(SAVE_NAME, &contents);
+    }
+}
+
+#[derive(Component)]
+struct Collectible;
+
+#[derive(Component)]
+struct CollectibleHudText;
+
+fn spawn_collectibles(
+    new_level: On<NewLevel>,
+    mut commands: Commands,
+    mut progress: ResMut<CollectibleProgress>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    progress.collected = 0;
+
+    let mesh = meshes.add(Circle::new(COLLECTIBLE_RADIUS));
+    let material = materials.add(COLLECTIBLE_COLOR);
+
+    for position in level_data(new_level.0).collectibles {
+        commands
+            .spawn((
+                Name::new("collectible"),
+                DestroyOnNewLevel,
+                DespawnOnExit(Screen::Gameplay),
+                Collectible,
+                Mesh2d(mesh.clone()),
+                MeshMaterial2d(material.clone()),
+                Collider::circle(COLLECTIBLE_RADIUS),
+                RigidBody::Static,
+                PlayerTrigger,
+                Transform::from_translation(position.extend(COLLECTIBLE_Z)),
+            ))
+            .observe(on_collectible_picked_up);
+    }
+}
+
+fn record_reached_level(new_level: On<NewLevel>, mut progress: ResMut<CollectibleProgress>) {
+    // Waking up is not a level which can be started.
+    let level = new_level.0.min(WAKE_UP_LEVEL - 1);
+    if level > progress.reached {
+        progress.reached = level;
+        progress.save();
+    }
+}
+
+fn on_collectible_picked_up(
+    event: On<PlayerEntered>,
+    mut commands: Commands,
+    assets: Res<AllAssets>,
+    mut progress: ResMut<CollectibleProgress>,
+) {
+    commands.entity(event.entity).despawn();
+    commands.spawn(sound_effect_complex(assets.goal_reached.clone(), 0.3, 2.0));
+    progress.collected += 1;
+}
+
+fn record_best_on_level_completed(
+    completed: On<LevelCompleted>,
+    mut progress: ResMut<CollectibleProgress>,
+) {
+    let Some(&best) = progress.best.get(completed.0) else {
+        return;
+    };
+
+    if progress.collected > best {
+        progress.best[completed.0] = progress.collected;
+        progress.save();
+    }
+}
+
+fn spawn_collectible_hud(mut commands: Commands, assets: Res<AllAssets>) {
+    commands.spawn((
+        Name::new("Collectible HUD"),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Vw(2.0),
+            top: Val::Vh(2.0),
+            ..default()
+        },
+        Text::default(),
+        TextFont {
+            font: assets.catex_fx_bold.clone(),
+            font_size: 28.0,
+            ..default()
+        },
+        TextColor(COLLECTIBLE_COLOR),
+        Pickable::IGNORE,
+        CollectibleHudText,
+        DespawnOnExit(Screen::Gameplay),
+    ));
+}
+
+fn update_collectible_hud(
+    progress: Res<CollectibleProgress>,
+    level_number: Res<LevelNumber>,
+    localizer: Localizer,
+    hud: Single<(&mut Text, &mut Visibility), With<CollectibleHudText>>,
+) {
+    let (mut text, mut visibility) = hud.into_inner();
+    let total = level_data(level_number.0).collectibles.len();
+
+    *visibility = if total == 0 {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+    text.0 = localizer.format("hud.treats", &[&progress.collected, &total]);
+}
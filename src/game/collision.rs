@@ -0,0 +1,78 @@
+//! Collision layers, and sensors reacting to the player.
+//!
+//! An entity with a [`PlayerTrigger`] is notified with [`PlayerEntered`] and [`PlayerExited`]
+//! events when the player starts and stops touching it, ignoring every other collider.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::game::player::Player;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(trigger_player_entered)
+        .add_observer(trigger_player_exited);
+}
+
+#[derive(PhysicsLayer, Default)]
+pub enum GameLayer {
+    /// Walls and other solid parts of a level.
+    #[default]
+    World,
+    Player,
+    /// Anything that hurts the player.
+    Hazard,
+    /// Sensors which only detect other colliders, like a [`PlayerTrigger`].
+    Trigger,
+}
+
+/// A sensor notified when the player enters or exits it.
+///
+/// Other collision layers can be given to a trigger which should also be detected by other
+/// sensors, as long as it still interacts with the player.
+#[derive(Component, Default)]
+#[require(
+    Sensor,
+    CollisionEventsEnabled,
+    CollisionLayers::new(GameLayer::Trigger, GameLayer::Player)
+)]
+pub struct PlayerTrigger;
+
+/// The player started touching the [`PlayerTrigger`].
+#[derive(EntityEvent)]
+pub struct PlayerEntered {
+    pub entity: Entity,
+}
+
+/// The player stopped touching the [`PlayerTrigger`].
+#[derive(EntityEvent)]
+pub struct PlayerExited {
+    pub entity: Entity,
+}
+
+// Collision events of a trigger are triggered on the trigger as `collider1`.
+
+fn trigger_player_entered(
+    event: On<CollisionStart>,
+    mut commands: Commands,
+    triggers: Query<(), With<PlayerTrigger>>,
+    players: Query<(), With<Player>>,
+) {
+    if triggers.contains(event.collider1) && players.contains(event.collider2) {
+        commands.trigger(PlayerEntered {
+            entity: event.collider1,
+        });
+    }
+}
+
+fn trigger_player_exited(
+    event: On<CollisionEnd>,
+    mut commands: Commands,
+    triggers: Query<(), With<PlayerTrigger>>,
+    players: Query<(), With<Player>>,
+) {
+    if triggers.contains(event.collider1) && players.contains(event.collider2) {
+        commands.trigger(PlayerExited {
+            entity: event.collider1,
+        });
+    }
+}
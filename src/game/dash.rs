@@ -0,0 +1,239 @@
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems, Pause,
+    game::{AllAssets, DestroyOnNewLevel, hit_reaction::HitReaction, player::Player},
+    input::{Action, ActionState, ReadActions},
+    localization::LocalizedText,
+    screens::Screen,
+};
+
+const DASH_SPEED: f32 = 1800.0;
+const DASH_DURATION: f32 = 0.15;
+const DASH_COOLDOWN: f32 = 1.0;
+/// Whether the player can't be hit while dashing.
+const DASH_INVULNERABILITY: bool = true;
+
+const DUST_PERIOD: f32 = 0.02;
+const DUST_LIFETIME: f32 = 0.4;
+const DUST_SIZE: f32 = 24.0;
+const DUST_COLOR: Color = Color::srgba(0.85, 0.8, 0.7, 0.6);
+const DUST_Z: f32 = 90.0;
+
+const HUD_BAR_SIZE: Vec2 = Vec2::new(160.0, 12.0);
+const HUD_READY_COLOR: Color = Color::srgb(0.95, 0.85, 0.4);
+const HUD_CHARGING_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_dash_hud)
+        .add_systems(
+            PreUpdate,
+            request_dash
+                .after(ReadActions)
+                .run_if(in_state(Pause(false))),
+        )
+        .add_systems(
+            FixedUpdate,
+            (tick_dash, start_dash, spawn_dust_trail)
+                .chain()
+                .in_set(AppSystems::Update)
+                .in_set(PausableSystems),
+        )
+        .add_systems(
+            Update,
+            (fade_dust, update_dash_hud)
+                .in_set(AppSystems::Update)
+                .in_set(PausableSystems),
+        );
+}
+
+/// Short burst of speed in the current movement direction.
+#[derive(Component)]
+pub struct Dash {
+    duration: Timer,
+    cooldown: Timer,
+    dust: Timer,
+    direction: Vec2,
+    /// Whether dash was pressed since the last tick.
+    requested: bool,
+}
+
+impl Default for Dash {
+    fn default() -> Self {
+        let finished = |duration| {
+            let mut timer = Timer::from_seconds(duration, TimerMode::Once);
+            timer.finish();
+            timer
+        };
+
+        Self {
+            duration: finished(DASH_DURATION),
+            cooldown: finished(DASH_COOLDOWN),
+            dust: Timer::from_seconds(DUST_PERIOD, TimerMode::Repeating),
+            direction: Vec2::ZERO,
+            requested: false,
+        }
+    }
+}
+
+impl Dash {
+    pub fn is_dashing(&self) -> bool {
+        !self.duration.is_finished()
+    }
+
+    pub fn velocity(&self) -> Vec2 {
+        self.direction * DASH_SPEED
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.requested
+    }
+
+    /// Replaces the dash request read this tick with a recorded one.
+    pub fn replay_request(&mut self, requested: bool) {
+        self.requested = requested;
+    }
+}
+
+#[derive(Component)]
+struct DustPuff(Timer);
+
+#[derive(Component)]
+struct DashHudFill;
+
+fn tick_dash(time: Res<Time>, mut dash: Single<&mut Dash>) {
+    dash.duration.tick(time.delta());
+    dash.cooldown.tick(time.delta());
+}
+
+/// Keeps a dash press until the next tick, which may not run in the frame it was pressed.
+fn request_dash(actions: Res<ActionState>, mut dash: Single<&mut Dash>) {
+    if actions.just_pressed(Action::Dash) {
+        dash.requested = true;
+    }
+}
+
+fn start_dash(player: Single<(&Player, &mut Dash, &mut HitReaction)>) {
+    let (player, mut dash, mut reaction) = player.into_inner();
+
+    let requested = std::mem::take(&mut dash.requested);
+    if !requested
+        || player.disable_movement
+        || player.movement_direction() == Vec2::ZERO
+        || !dash.cooldown.is_finished()
+    {
+        return;
+    }
+
+    dash.direction = player.movement_direction().normalize();
+    dash.duration.reset();
+    dash.cooldown.reset();
+
+    if DASH_INVULNERABILITY {
+        reaction.extend_invulnerability(DASH_DURATION);
+    }
+}
+
+fn spawn_dust_trail(
+    mut commands: Commands,
+    time: Res<Time>,
+    player: Single<(&mut Dash, &Transform)>,
+) {
+    let (mut dash, transform) = player.into_inner();
+    if !dash.is_dashing() {
+        return;
+    }
+
+    dash.dust.tick(time.delta());
+    if !dash.dust.just_finished() {
+        return;
+    }
+
+    commands.spawn((
+        Name::new("dust puff"),
+        DestroyOnNewLevel,
+        DespawnOnExit(Screen::Gameplay),
+        DustPuff(Timer::from_seconds(DUST_LIFETIME, TimerMode::Once)),
+        Sprite::from_color(DUST_COLOR, Vec2::splat(DUST_SIZE)),
+        Transform::from_translation(transform.translation.with_z(DUST_Z)),
+    ));
+}
+
+fn fade_dust(
+    mut commands: Commands,
+    time: Res<Time>,
+    puffs: Query<(Entity, &mut DustPuff, &mut Sprite, &mut Transform)>,
+) {
+    for (entity, mut puff, mut sprite, mut transform) in puffs {
+        puff.0.tick(time.delta());
+        if puff.0.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let remaining = puff.0.fraction_remaining();
+        sprite.color = DUST_COLOR.with_alpha(DUST_COLOR.alpha() * remaining);
+        transform.scale = Vec3::splat(0.5 + remaining);
+    }
+}
+
+fn spawn_dash_hud(mut commands: Commands, assets: Res<AllAssets>) {
+    commands.spawn((
+        Name::new("Dash HUD"),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Vw(2.0),
+            bottom: Val::Vh(3.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: px(6),
+            ..default()
+        },
+        Pickable::IGNORE,
+        DespawnOnExit(Screen::Gameplay),
+        children![
+            (
+                LocalizedText::new("hud.dash"),
+                TextFont {
+                    font: assets.catex_fx_bold.clone(),
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(Color::linear_rgb(1.0, 1.0, 1.0)),
+                Pickable::IGNORE,
+            ),
+            (
+                Node {
+                    width: px(HUD_BAR_SIZE.x),
+                    height: px(HUD_BAR_SIZE.y),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                Pickable::IGNORE,
+                children![(
+                    Node {
+                        width: percent(100),
+                        height: percent(100),
+                        ..default()
+                    },
+                    BackgroundColor(HUD_READY_COLOR),
+                    Pickable::IGNORE,
+                    DashHudFill,
+                )],
+            ),
+        ],
+    ));
+}
+
+fn update_dash_hud(
+    dash: Single<&Dash>,
+    fill: Single<(&mut Node, &mut BackgroundColor), With<DashHudFill>>,
+) {
+    let (mut node, mut color) = fill.into_inner();
+
+    node.width = percent(100.0 * dash.cooldown.fraction());
+    color.0 = if dash.cooldown.is_finished() {
+        HUD_READY_COLOR
+    } else {
+        HUD_CHARGING_COLOR
+    };
+}
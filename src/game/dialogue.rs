@@ -0,0 +1,533 @@
+//! Dialogue sequences between the cat, the bed and the dream voice.
+//!
+//! Dialogues are loaded from `.dialogue` files, see `assets/dialogues/story.dialogue` for the
+//! format. While a dialogue is open, gameplay is paused.
+
+use std::collections::{HashMap, HashSet};
+
+use avian2d::prelude::*;
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    ecs::spawn::SpawnIter,
+    prelude::*,
+};
+
+use crate::{
+    Pause,
+    game::{
+        AllAssets, DestroyOnNewLevel, NewLevel,
+        collision::{PlayerEntered, PlayerTrigger},
+        environment::ROAD_SIZE,
+        level_data::level_data,
+    },
+    input::{Action, ActionState},
+    localization::LocalizedText,
+    menus::Menu,
+    screens::Screen,
+    theme::{interaction::Activate, widget},
+};
+
+const DIALOGUE_TRIGGER_HEIGHT: f32 = 100.0;
+const PORTRAIT_SIZE: f32 = 128.0;
+const PANEL_COLOR: Color = Color::srgba(0.05, 0.02, 0.1, 0.9);
+const DREAM_PORTRAIT_COLOR: Color = Color::srgb(0.6, 0.4, 0.9);
+/// The first animation frame of the cat and bed sprite sheets.
+const PORTRAIT_FRAME: Rect = Rect {
+    min: Vec2::ZERO,
+    max: Vec2::splat(64.0),
+};
+/// Target of a choice which closes the dialogue.
+const END_TARGET: &str = "end";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<DialogueSet>()
+        .register_asset_loader(DialogueLoader)
+        .init_resource::<ActiveDialogue>()
+        .init_resource::<SeenDialogues>()
+        .add_systems(OnEnter(Screen::Gameplay), reset_seen_dialogues)
+        .add_systems(OnEnter(Menu::Dialogue), spawn_dialogue_box)
+        .add_systems(
+            Update,
+            (advance_dialogue, choose_with_keyboard, update_dialogue_box)
+                .chain()
+                .run_if(in_state(Menu::Dialogue)),
+        )
+        .add_observer(start_dialogue)
+        .add_observer(spawn_dialogue_triggers);
+}
+
+/// Where a dialogue starts when the player walks over it.
+pub struct DialoguePlacement {
+    pub position: Vec2,
+    pub id: &'static str,
+}
+
+/// Opens the dialogue with the given id.
+#[derive(Event)]
+pub struct StartDialogue(pub String);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Speaker {
+    Cat,
+    Bed,
+    Dream,
+}
+
+impl Speaker {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "cat" => Some(Self::Cat),
+            "bed" => Some(Self::Bed),
+            "dream" => Some(Self::Dream),
+            _ => None,
+        }
+    }
+
+    /// Localization key of the speaker's name.
+    fn label(self) -> &'static str {
+        match self {
+            Self::Cat => "speaker.cat",
+            Self::Bed => "speaker.bed",
+            Self::Dream => "speaker.dream",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DialogueLine {
+    speaker: Speaker,
+    /// Localization key of the line.
+    key: String,
+}
+
+#[derive(Debug)]
+struct DialogueChoice {
+    /// Localization key of the choice.
+    key: String,
+    target: String,
+}
+
+/// Lines said one after another, optionally followed by choices shown with the last line.
+#[derive(Debug, Default)]
+struct Dialogue {
+    lines: Vec<DialogueLine>,
+    choices: Vec<DialogueChoice>,
+}
+
+#[derive(Asset, TypePath, Debug, Default)]
+pub struct DialogueSet {
+    dialogues: HashMap<String, Dialogue>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DialogueLoaderError {
+    #[error("could not read dialogue file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("dialogue file is not valid UTF-8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+    #[error("line {0}: expected a dialogue id before its content")]
+    MissingId(usize),
+    #[error("line {0}: unknown speaker `{1}`")]
+    UnknownSpeaker(usize, String),
+    #[error("line {0}: expected `speaker: key` or `> key -> target`")]
+    InvalidLine(usize),
+}
+
+#[derive(TypePath)]
+struct DialogueLoader;
+
+impl AssetLoader for DialogueLoader {
+    type Asset = DialogueSet;
+    type Settings = ();
+    type Error = DialogueLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<DialogueSet, DialogueLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        parse_dialogues(&String::from_utf8(bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["dialogue"]
+    }
+}
+
+fn parse_dialogues(source: &str) -> Result<DialogueSet, DialogueLoaderError> {
+    let mut set = DialogueSet::default();
+    let mut current = None;
+
+    for (index, line) in source.lines().enumerate() {
+        let number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(id) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            set.dialogues
+                .insert(id.trim().to_string(), Dialogue::default());
+            current = Some(id.trim().to_string());
+            continue;
+        }
+
+        let Some(dialogue) = current.as_ref().and_then(|id| set.dialogues.get_mut(id)) else {
+            return Err(DialogueLoaderError::MissingId(number));
+        };
+
+        if let Some(choice) = line.strip_prefix('>') {
+            let (key, target) = choice
+                .split_once("->")
+                .ok_or(DialogueLoaderError::InvalidLine(number))?;
+            dialogue.choices.push(DialogueChoice {
+                key: key.trim().to_string(),
+                target: target.trim().to_string(),
+            });
+            continue;
+        }
+
+        let (speaker, key) = line
+            .split_once(':')
+            .ok_or(DialogueLoaderError::InvalidLine(number))?;
+        let speaker = Speaker::parse(speaker.trim())
+            .ok_or_else(|| DialogueLoaderError::UnknownSpeaker(number, speaker.to_string()))?;
+        dialogue.lines.push(DialogueLine {
+            speaker,
+            key: key.trim().to_string(),
+        });
+    }
+
+    Ok(set)
+}
+
+/// The dialogue being shown and the index of its current line.
+#[derive(Resource, Default)]
+struct ActiveDialogue {
+    id: String,
+    line: usize,
+}
+
+/// Dialogue triggers already walked over during this visit to [`Screen::Gameplay`].
+#[derive(Resource, Default)]
+struct SeenDialogues(HashSet<&'static str>);
+
+#[derive(Component)]
+struct DialogueTrigger(&'static str);
+
+#[derive(Component)]
+struct DialoguePortrait;
+
+#[derive(Component)]
+struct DialogueSpeakerLabel;
+
+#[derive(Component)]
+struct DialogueTextLabel;
+
+#[derive(Component)]
+struct DialogueChoices;
+
+fn reset_seen_dialogues(mut seen: ResMut<SeenDialogues>) {
+    seen.0.clear();
+}
+
+fn spawn_dialogue_triggers(new_level: On<NewLevel>, mut commands: Commands) {
+    for placement in level_data(new_level.0).dialogues {
+        commands
+            .spawn((
+                Name::new("dialogue trigger"),
+                DialogueTrigger(placement.id),
+                DestroyOnNewLevel,
+                DespawnOnExit(Screen::Gameplay),
+                Collider::rectangle(ROAD_SIZE.x, DIALOGUE_TRIGGER_HEIGHT),
+                RigidBody::Static,
+                PlayerTrigger,
+                Transform::from_translation(placement.position.extend(0.0)),
+            ))
+            .observe(on_player_enters_trigger);
+    }
+}
+
+fn on_player_enters_trigger(
+    event: On<PlayerEntered>,
+    mut commands: Commands,
+    mut seen: ResMut<SeenDialogues>,
+    triggers: Query<&DialogueTrigger>,
+) {
+    let Ok(trigger) = triggers.get(event.entity) else {
+        return;
+    };
+
+    commands.entity(event.entity).despawn();
+    if seen.0.insert(trigger.0) {
+        commands.trigger(StartDialogue(trigger.0.to_string()));
+    }
+}
+
+fn start_dialogue(
+    event: On<StartDialogue>,
+    assets: Res<AllAssets>,
+    dialogue_sets: Res<Assets<DialogueSet>>,
+    mut active: ResMut<ActiveDialogue>,
+    mut next_menu: ResMut<NextState<Menu>>,
+    mut next_pause: ResMut<NextState<Pause>>,
+) {
+    let Some(set) = dialogue_sets.get(&assets.dialogues) else {
+        return;
+    };
+    if !set.dialogues.contains_key(&event.0) {
+        warn!("Unknown dialogue `{}`", event.0);
+        return;
+    }
+
+    *active = ActiveDialogue {
+        id: event.0.clone(),
+        line: 0,
+    };
+    next_menu.set(Menu::Dialogue);
+    next_pause.set(Pause(true));
+}
+
+fn spawn_dialogue_box(mut commands: Commands, assets: Res<AllAssets>) {
+    commands.spawn((
+        Name::new("Dialogue Box"),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Vw(10.0),
+            right: Val::Vw(10.0),
+            bottom: Val::Vh(5.0),
+            padding: UiRect::all(px(20)),
+            column_gap: px(20),
+            border_radius: BorderRadius::all(px(16)),
+            ..default()
+        },
+        BackgroundColor(PANEL_COLOR),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Dialogue),
+        children![
+            (
+                Name::new("Portrait"),
+                Node {
+                    width: px(PORTRAIT_SIZE),
+                    height: px(PORTRAIT_SIZE),
+                    flex_shrink: 0.0,
+                    ..default()
+                },
+                ImageNode::default(),
+                DialoguePortrait,
+            ),
+            (
+                Name::new("Dialogue Content"),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    flex_grow: 1.0,
+                    row_gap: px(10),
+                    ..default()
+                },
+                children![
+                    (
+                        LocalizedText::new(""),
+                        TextFont {
+                            font: assets.catex_fx_bold.clone(),
+                            font_size: 32.0,
+                            ..default()
+                        },
+                        DialogueSpeakerLabel,
+                    ),
+                    (widget::label(""), DialogueTextLabel),
+                    (
+                        Name::new("Dialogue Choices"),
+                        Node {
+                            flex_direction: FlexDirection::Column,
+                            row_gap: px(10),
+                            ..default()
+                        },
+                        DialogueChoices,
+                    ),
+                ],
+            ),
+        ],
+    ));
+}
+
+fn current_dialogue<'a>(
+    assets: &AllAssets,
+    dialogue_sets: &'a Assets<DialogueSet>,
+    active: &ActiveDialogue,
+) -> Option<&'a Dialogue> {
+    dialogue_sets
+        .get(&assets.dialogues)?
+        .dialogues
+        .get(&active.id)
+}
+
+/// Follows a choice, or closes the dialogue when given the end target.
+fn go_to_dialogue(
+    target: &str,
+    dialogue_sets: &Assets<DialogueSet>,
+    assets: &AllAssets,
+    active: &mut ActiveDialogue,
+    next_menu: &mut NextState<Menu>,
+) {
+    let known = dialogue_sets
+        .get(&assets.dialogues)
+        .is_some_and(|set| set.dialogues.contains_key(target));
+
+    if target == END_TARGET || !known {
+        if target != END_TARGET {
+            warn!("Unknown dialogue `{target}`");
+        }
+        next_menu.set(Menu::None);
+        return;
+    }
+
+    *active = ActiveDialogue {
+        id: target.to_string(),
+        line: 0,
+    };
+}
+
+fn advance_dialogue(
+    actions: Res<ActionState>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    assets: Res<AllAssets>,
+    dialogue_sets: Res<Assets<DialogueSet>>,
+    mut active: ResMut<ActiveDialogue>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    if !actions.just_pressed(Action::Interact) && !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let Some(dialogue) = current_dialogue(&assets, &dialogue_sets, &active) else {
+        next_menu.set(Menu::None);
+        return;
+    };
+
+    if active.line + 1 < dialogue.lines.len() {
+        active.line += 1;
+    } else if dialogue.choices.is_empty() {
+        next_menu.set(Menu::None);
+    }
+}
+
+fn choose_with_keyboard(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    assets: Res<AllAssets>,
+    dialogue_sets: Res<Assets<DialogueSet>>,
+    mut active: ResMut<ActiveDialogue>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    const CHOICE_KEYS: [KeyCode; 4] = [
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+    ];
+
+    let Some(dialogue) = current_dialogue(&assets, &dialogue_sets, &active) else {
+        return;
+    };
+    if active.line + 1 < dialogue.lines.len() {
+        return;
+    }
+
+    let Some(choice) = CHOICE_KEYS
+        .iter()
+        .zip(&dialogue.choices)
+        .find_map(|(&key, choice)| keyboard.just_pressed(key).then_some(choice))
+    else {
+        return;
+    };
+
+    go_to_dialogue(
+        &choice.target,
+        &dialogue_sets,
+        &assets,
+        &mut active,
+        &mut next_menu,
+    );
+}
+
+fn choose(
+    target: String,
+) -> impl FnMut(
+    On<Activate>,
+    Res<AllAssets>,
+    Res<Assets<DialogueSet>>,
+    ResMut<ActiveDialogue>,
+    ResMut<NextState<Menu>>,
+) {
+    move |_, assets, dialogue_sets, mut active, mut next_menu| {
+        go_to_dialogue(
+            &target,
+            &dialogue_sets,
+            &assets,
+            &mut active,
+            &mut next_menu,
+        );
+    }
+}
+
+fn update_dialogue_box(
+    mut commands: Commands,
+    assets: Res<AllAssets>,
+    dialogue_sets: Res<Assets<DialogueSet>>,
+    active: Res<ActiveDialogue>,
+    mut portrait: Single<&mut ImageNode, With<DialoguePortrait>>,
+    mut speaker_label: Single<&mut LocalizedText, With<DialogueSpeakerLabel>>,
+    mut text_label: Single<
+        &mut LocalizedText,
+        (With<DialogueTextLabel>, Without<DialogueSpeakerLabel>),
+    >,
+    choices: Single<Entity, With<DialogueChoices>>,
+) {
+    if !active.is_changed() {
+        return;
+    }
+    let Some(dialogue) = current_dialogue(&assets, &dialogue_sets, &active) else {
+        return;
+    };
+    let Some(line) = dialogue.lines.get(active.line) else {
+        return;
+    };
+
+    **portrait = match line.speaker {
+        Speaker::Cat => ImageNode::new(assets.cat.clone()).with_rect(PORTRAIT_FRAME),
+        Speaker::Bed => ImageNode::new(assets.cat_bed.clone()).with_rect(PORTRAIT_FRAME),
+        Speaker::Dream => ImageNode::solid_color(DREAM_PORTRAIT_COLOR),
+    };
+
+    **speaker_label = LocalizedText::new(line.speaker.label());
+    **text_label = LocalizedText::new(line.key.clone());
+
+    commands.entity(*choices).despawn_children();
+    if active.line + 1 == dialogue.lines.len() {
+        let buttons: Vec<_> = dialogue
+            .choices
+            .iter()
+            .enumerate()
+            .map(|(index, choice)| {
+                (
+                    LocalizedText::new("dialogue.choice")
+                        .with_arg(index + 1)
+                        .with_localized_arg(choice.key.clone()),
+                    choice.target.clone(),
+                )
+            })
+            .collect();
+
+        commands.entity(*choices).insert(Children::spawn(SpawnIter(
+            buttons
+                .into_iter()
+                .map(|(text, target)| widget::button(text, choose(target))),
+        )));
+    }
+}
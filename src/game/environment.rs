@@ -7,6 +7,7 @@ use rand::Rng;
 use crate::{
     game::{AllAssets, DestroyOnNewLevel, NewLevel, RandomSource},
     screens::Screen,
+    tuning::{Tuning, init_tuning},
 };
 
 pub const ROAD_SIZE: Vec2 = Vec2::new(1200.0, 8192.0);
@@ -15,19 +16,44 @@ const ROAD_Z: f32 = -100.0;
 pub const WALL_Z: f32 = 200.0;
 const WALL_THICKNESS: f32 = 4096.0;
 
-const STONE_COUNT: usize = 200;
-const STONE_SCALE: f32 = 2.0;
 const STONE_Z: f32 = -50.0;
 const MOVE_HINT_Z: f32 = -75.0;
 
 pub fn plugin(app: &mut App) {
+    init_tuning::<StoneTuning>(app);
+
     app.add_observer(spawn_road).add_observer(spawn_walls);
 }
 
+/// Tunable values of the stones scattered over the road, see [`crate::tuning`].
+#[derive(Resource, Reflect, Clone, Debug)]
+#[reflect(Resource)]
+pub struct StoneTuning {
+    pub count: usize,
+    pub scale: f32,
+    /// How much the scale of a stone randomly differs from `scale`, as a fraction of it.
+    pub scale_variation: f32,
+}
+
+impl Default for StoneTuning {
+    fn default() -> Self {
+        Self {
+            count: 200,
+            scale: 2.0,
+            scale_variation: 0.3,
+        }
+    }
+}
+
+impl Tuning for StoneTuning {
+    const SECTION: &'static str = "stone";
+}
+
 fn spawn_road(
     _: On<NewLevel>,
     mut commands: Commands,
     assets: Res<AllAssets>,
+    tuning: Res<StoneTuning>,
     mut random_source: ResMut<RandomSource>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
@@ -51,13 +77,14 @@ fn spawn_road(
             Transform::from_xyz(0.0, 500.0, MOVE_HINT_Z),
         ));
 
-    for _ in 0..STONE_COUNT {
+    let variation = tuning.scale_variation.clamp(0.0, 1.0);
+    for _ in 0..tuning.count {
         let stone_index = random_source.0.random_range(0..3);
         let image = assets.stones[stone_index].clone();
 
         let position = road_rectangle.sample_interior(&mut random_source.0);
         let rotation = random_source.0.random_range(0.0..2.0 * PI);
-        let scale = STONE_SCALE * random_source.0.random_range(0.7..=1.3);
+        let scale = tuning.scale * random_source.0.random_range(1.0 - variation..=1.0 + variation);
 
         let position = position + Vec2::Y * ROAD_SIZE.y / 2.0;
 
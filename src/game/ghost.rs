@@ -0,0 +1,200 @@
+//! A translucent ghost cat replaying the fastest completed run of the current level.
+//!
+//! The ghost follows the player positions of the [`Recording`]. When a level is completed faster
+//! than before, its part of the recording is saved as the new personal best of that level.
+
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    game::{
+        AllAssets, DestroyOnNewLevel, LevelCompleted, LevelNumber, NewLevel, WAKE_UP_LEVEL,
+        animation::SpriteAnimation,
+        player::{PLAYER_Z, PlayerTuning, player_sprite},
+        replay::{Recording, Replay},
+    },
+    persistence,
+    screens::Screen,
+};
+
+const SHOW_GHOST_SAVE_NAME: &str = "show_ghost";
+
+const GHOST_COLOR: Color = Color::srgba(0.7, 0.85, 1.0, 0.4);
+const GHOST_Z: f32 = PLAYER_Z - 1.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(ShowGhost::load())
+        .init_resource::<AttemptStart>()
+        .insert_resource(BestRuns::load())
+        .add_systems(
+            Update,
+            (play_ghost.in_set(PausableSystems), update_ghost_visibility)
+                .run_if(in_state(Screen::Gameplay)),
+        )
+        .add_observer(spawn_ghost)
+        .add_observer(save_best_run);
+}
+
+/// Whether the ghost of the personal best is shown.
+#[derive(Resource)]
+pub struct ShowGhost(pub bool);
+
+impl Default for ShowGhost {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+impl ShowGhost {
+    fn load() -> Self {
+        persistence::load(SHOW_GHOST_SAVE_NAME)
+            .map_or_else(Self::default, |saved| Self(saved.trim() == "on"))
+    }
+
+    pub fn save(&self) {
+        persistence::save(SHOW_GHOST_SAVE_NAME, if self.0 { "on" } else { "off" });
+    }
+}
+
+fn save_name(level: usize) -> String {
+    format!("ghost_{level}")
+}
+
+/// The fastest completed run of each level.
+#[derive(Resource)]
+struct BestRuns([Option<Replay>; WAKE_UP_LEVEL]);
+
+impl BestRuns {
+    fn load() -> Self {
+        Self(std::array::from_fn(|level| {
+            Replay::load(&save_name(level)).filter(|run| !run.frames.is_empty())
+        }))
+    }
+}
+
+/// Tick of the [`Recording`] at which the current attempt at the level started.
+#[derive(Resource, Default)]
+struct AttemptStart(usize);
+
+#[derive(Component, Default)]
+struct Ghost {
+    time: f32,
+}
+
+fn spawn_ghost(
+    new_level: On<NewLevel>,
+    mut commands: Commands,
+    assets: Res<AllAssets>,
+    best_runs: Res<BestRuns>,
+    show_ghost: Res<ShowGhost>,
+    tuning: Res<PlayerTuning>,
+    recording: Res<Recording>,
+    mut attempt_start: ResMut<AttemptStart>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    attempt_start.0 = recording.ticks();
+
+    let Some(Some(run)) = best_runs.0.get(new_level.0) else {
+        return;
+    };
+    let start = run.frames[0];
+
+    let (mut sprite, animation) = player_sprite(new_level.0, &assets, &mut layouts);
+    sprite.color = GHOST_COLOR;
+
+    commands.spawn((
+        Name::new("ghost"),
+        Ghost::default(),
+        sprite,
+        animation,
+        Transform::from_translation(start.position.extend(GHOST_Z))
+            .with_rotation(Quat::from_rotation_z(start.rotation))
+            .with_scale(Vec3::splat(tuning.scale)),
+        if show_ghost.0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        },
+        DestroyOnNewLevel,
+        DespawnOnExit(Screen::Gameplay),
+    ));
+}
+
+fn save_best_run(
+    completed: On<LevelCompleted>,
+    recording: Res<Recording>,
+    attempt_start: Res<AttemptStart>,
+    mut best_runs: ResMut<BestRuns>,
+) {
+    let Some(best) = best_runs.0.get_mut(completed.0) else {
+        return;
+    };
+
+    // Every frame is a tick, so the run with fewer frames is the faster one.
+    let run = recording.since(attempt_start.0, completed.0);
+    if run.frames.is_empty()
+        || best
+            .as_ref()
+            .is_some_and(|best| best.frames.len() <= run.frames.len())
+    {
+        return;
+    }
+
+    run.save(&save_name(completed.0));
+    *best = Some(run);
+}
+
+fn play_ghost(
+    time: Res<Time>,
+    fixed_time: Res<Time<Fixed>>,
+    level: Res<LevelNumber>,
+    best_runs: Res<BestRuns>,
+    ghosts: Query<(&mut Ghost, &mut Transform, &mut SpriteAnimation)>,
+) {
+    let Some(Some(run)) = best_runs.0.get(level.0) else {
+        return;
+    };
+
+    for (mut ghost, mut transform, mut animation) in ghosts {
+        ghost.time += time.delta_secs();
+
+        // Frames are recorded every tick, so the ghost is eased between them to move smoothly at
+        // any frame rate. It stays where the run ended.
+        let tick = ghost.time / fixed_time.timestep().as_secs_f32();
+        let index = (tick as usize).min(run.frames.len() - 1);
+        let frame = run.frames[index];
+        let (position, rotation) = match run.frames.get(index + 1) {
+            Some(next) => {
+                let t = tick - index as f32;
+                (
+                    frame.position.lerp(next.position, t),
+                    Quat::from_rotation_z(frame.rotation)
+                        .slerp(Quat::from_rotation_z(next.rotation), t),
+                )
+            }
+            None => (frame.position, Quat::from_rotation_z(frame.rotation)),
+        };
+        let finished = index + 1 >= run.frames.len();
+
+        transform.translation = position.extend(GHOST_Z);
+        transform.rotation = rotation;
+        animation.paused = frame.movement == Vec2::ZERO || finished;
+    }
+}
+
+fn update_ghost_visibility(
+    show_ghost: Res<ShowGhost>,
+    ghosts: Query<&mut Visibility, With<Ghost>>,
+) {
+    if !show_ghost.is_changed() {
+        return;
+    }
+
+    for mut visibility in ghosts {
+        *visibility = if show_ghost.0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
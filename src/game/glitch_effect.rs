@@ -19,6 +19,10 @@ const EFFECT_DURATION: f32 = 1.5;
 pub fn plugin(app: &mut App) {
     app.add_plugins(FullscreenMaterialPlugin::<GlitchEffect>::default())
         .add_observer(spawn_glitch_effect)
+        .add_systems(
+            FixedUpdate,
+            update_glitch_transition.in_set(PausableSystems),
+        )
         .add_systems(Update, update_glitch_effect.in_set(PausableSystems));
 }
 
@@ -30,8 +34,11 @@ pub struct GlitchEffect {
     progress: f32,
 }
 
-#[derive(Component)]
-struct GlitchEffectMarker;
+/// A running glitch transition, from `0.0` to `1.0`. Movement is disabled until it ends.
+#[derive(Component, Default)]
+struct GlitchTransition {
+    progress: f32,
+}
 
 impl FullscreenMaterial for GlitchEffect {
     fn fragment_shader() -> ShaderRef {
@@ -48,22 +55,32 @@ impl FullscreenMaterial for GlitchEffect {
 }
 
 fn spawn_glitch_effect(_: On<SpawnGlitchEffect>, mut commands: Commands) {
-    commands.spawn(GlitchEffectMarker);
+    commands.spawn(GlitchTransition::default());
 }
 
-fn update_glitch_effect(
+fn update_glitch_transition(
     mut commands: Commands,
     time: Res<Time>,
-    mut effect: Single<&mut GlitchEffect>,
-    marker: Single<Entity, With<GlitchEffectMarker>>,
+    transition: Single<(Entity, &mut GlitchTransition)>,
     mut player: Single<&mut Player>,
 ) {
-    effect.progress += time.delta_secs() / EFFECT_DURATION;
+    let (entity, mut transition) = transition.into_inner();
+    transition.progress += time.delta_secs() / EFFECT_DURATION;
     player.disable_movement = true;
 
-    if effect.progress > 1.0 {
-        effect.progress = 0.0;
-        commands.entity(*marker).despawn();
+    if transition.progress > 1.0 {
+        commands.entity(entity).despawn();
         player.disable_movement = false;
     }
 }
+
+/// Shows the progress of the transition on screen, or nothing without one.
+fn update_glitch_effect(
+    transition: Option<Single<&GlitchTransition>>,
+    mut effect: Single<&mut GlitchEffect>,
+) {
+    let progress = transition.map_or(0.0, |transition| transition.progress.min(1.0));
+    if effect.progress != progress {
+        effect.progress = progress;
+    }
+}
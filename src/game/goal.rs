@@ -4,7 +4,9 @@ use bevy::prelude::*;
 use crate::{
     audio::sound_effect_volume,
     game::{
-        AllAssets, DestroyOnNewLevel, LevelNumber, NewLevel, environment::ROAD_SIZE, player::Player,
+        AllAssets, DestroyOnNewLevel, LevelCompleted, LevelNumber, NewLevel,
+        collision::{PlayerEntered, PlayerTrigger},
+        environment::ROAD_SIZE,
     },
     screens::Screen,
 };
@@ -47,24 +49,19 @@ fn spawn_goal(
             Transform::from_xyz(0.0, ROAD_SIZE.y - 160.0, GOAL_Z)
                 .with_scale(Vec2::splat(4.0).extend(1.0)),
             collider,
-            Sensor,
-            CollisionEventsEnabled,
+            PlayerTrigger,
             Goal,
         ))
         .observe(on_player_reaches_goal);
 }
 
 fn on_player_reaches_goal(
-    event: On<CollisionStart>,
+    _: On<PlayerEntered>,
     mut commands: Commands,
     assets: Res<AllAssets>,
     mut level_number: ResMut<LevelNumber>,
-    player: Single<Entity, With<Player>>,
 ) {
-    if event.collider2 != *player {
-        return;
-    }
-
+    commands.trigger(LevelCompleted(level_number.0));
     level_number.0 += 1;
     commands.spawn(sound_effect_volume(assets.goal_reached.clone(), 0.5));
     commands.trigger(NewLevel(level_number.0));
@@ -1,17 +1,98 @@
+use std::collections::VecDeque;
+
 use bevy::prelude::*;
 
-use crate::{game::AllAssets, screens::Screen};
+use crate::{
+    PausableSystems,
+    game::{AllAssets, NewLevel},
+    localization::Localizer,
+    screens::Screen,
+};
+
+/// How long a fully revealed message stays before a queued one may replace it.
+const MIN_DISPLAY_DURATION: f32 = 2.0;
+/// How long a fully revealed message stays when nothing else is queued.
+const DISPLAY_DURATION: f32 = 5.0;
+const FADE_DURATION: f32 = 1.0;
+const TYPEWRITER_CHARS_PER_SECOND: f32 = 40.0;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Gameplay), spawn_guide)
-        .add_observer(change_text);
+    app.init_resource::<GuideQueue>()
+        .add_systems(OnEnter(Screen::Gameplay), spawn_guide)
+        .add_systems(
+            Update,
+            (advance_guide_queue, update_guide_text)
+                .chain()
+                .run_if(in_state(Screen::Gameplay))
+                .in_set(PausableSystems),
+        )
+        .add_observer(clear_queue_on_new_level)
+        .add_observer(queue_text);
 }
 
 #[derive(Component)]
 struct GuideText;
 
-#[derive(Event)]
-pub struct ChangeGuideText(pub String);
+/// Higher priorities interrupt the message being shown and skip ahead in the queue.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum GuidePriority {
+    Hint,
+    Story,
+    Warning,
+}
+
+#[derive(Event, Clone)]
+pub struct ChangeGuideText {
+    /// Localization key of the message.
+    pub key: String,
+    pub priority: GuidePriority,
+    /// Reveal the text one character at a time.
+    pub typewriter: bool,
+}
+
+impl ChangeGuideText {
+    pub fn new(key: impl Into<String>, priority: GuidePriority) -> Self {
+        Self {
+            key: key.into(),
+            priority,
+            typewriter: priority != GuidePriority::Warning,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+struct GuideQueue {
+    current: Option<(ChangeGuideText, f32)>,
+    queue: VecDeque<ChangeGuideText>,
+}
+
+impl GuideQueue {
+    fn show(&mut self, message: ChangeGuideText) {
+        self.current = Some((message, 0.0));
+    }
+
+    /// Queues the message after those of the same or a higher priority, or before those of the
+    /// same priority if it was interrupted while shown.
+    fn enqueue(&mut self, message: ChangeGuideText, interrupted: bool) {
+        let index = self
+            .queue
+            .iter()
+            .position(|queued| {
+                queued.priority < message.priority
+                    || (interrupted && queued.priority == message.priority)
+            })
+            .unwrap_or(self.queue.len());
+        self.queue.insert(index, message);
+    }
+}
+
+fn reveal_duration(message: &ChangeGuideText, localizer: &Localizer) -> f32 {
+    if message.typewriter {
+        localizer.get(&message.key).chars().count() as f32 / TYPEWRITER_CHARS_PER_SECOND
+    } else {
+        0.0
+    }
+}
 
 fn spawn_guide(mut commands: Commands, assets: Res<AllAssets>) {
     commands
@@ -31,7 +112,7 @@ fn spawn_guide(mut commands: Commands, assets: Res<AllAssets>) {
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text::new("You are a cat, your goal is to reach your bed."),
+                Text::default(),
                 TextFont {
                     font: assets.catex_fx_bold.clone(),
                     font_size: 40.0,
@@ -53,6 +134,68 @@ fn spawn_guide(mut commands: Commands, assets: Res<AllAssets>) {
         });
 }
 
-fn change_text(event: On<ChangeGuideText>, mut guide: Single<&mut Text, With<GuideText>>) {
-    guide.0 = event.0.clone();
+fn clear_queue_on_new_level(_: On<NewLevel>, mut guide: ResMut<GuideQueue>) {
+    *guide = GuideQueue::default();
+}
+
+fn queue_text(event: On<ChangeGuideText>, mut guide: ResMut<GuideQueue>) {
+    let message = event.event().clone();
+
+    let interrupts = match &guide.current {
+        Some((current, _)) => message.priority > current.priority,
+        None => true,
+    };
+    if !interrupts {
+        guide.enqueue(message, false);
+        return;
+    }
+
+    // The interrupted message is shown again from the start once its turn comes.
+    if let Some((interrupted, _)) = guide.current.take() {
+        guide.enqueue(interrupted, true);
+    }
+    guide.show(message);
+}
+
+fn advance_guide_queue(time: Res<Time>, localizer: Localizer, mut guide: ResMut<GuideQueue>) {
+    let has_queued = !guide.queue.is_empty();
+    let Some((message, elapsed)) = &mut guide.current else {
+        if let Some(next) = guide.queue.pop_front() {
+            guide.show(next);
+        }
+        return;
+    };
+
+    *elapsed += time.delta_secs();
+    let shown = *elapsed - reveal_duration(message, &localizer);
+
+    if has_queued && shown >= MIN_DISPLAY_DURATION {
+        let next = guide.queue.pop_front();
+        guide.current = next.map(|next| (next, 0.0));
+    } else if shown >= DISPLAY_DURATION + FADE_DURATION {
+        guide.current = None;
+    }
+}
+
+fn update_guide_text(
+    guide: Res<GuideQueue>,
+    localizer: Localizer,
+    text: Single<(&mut Text, &mut TextColor), With<GuideText>>,
+) {
+    let (mut text, mut color) = text.into_inner();
+
+    let Some((message, elapsed)) = &guide.current else {
+        text.0.clear();
+        return;
+    };
+
+    let revealed = if message.typewriter {
+        (elapsed * TYPEWRITER_CHARS_PER_SECOND) as usize
+    } else {
+        usize::MAX
+    };
+    text.0 = localizer.get(&message.key).chars().take(revealed).collect();
+
+    let fade = (elapsed - reveal_duration(message, &localizer) - DISPLAY_DURATION) / FADE_DURATION;
+    color.0.set_alpha(1.0 - fade.clamp(0.0, 1.0));
 }
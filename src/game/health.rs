@@ -0,0 +1,197 @@
+use bevy::prelude::*;
+
+use crate::{
+    audio::sound_effect_volume,
+    game::{
+        AllAssets, LevelNumber, LevelRestart, NewLevel, hit_reaction::HitReaction, player::Player,
+    },
+    localization::Localizer,
+    persistence,
+    screens::Screen,
+};
+
+const MAX_LIVES: usize = 3;
+const SAVE_NAME: &str = "difficulty";
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(Difficulty::load())
+        .init_resource::<Lives>()
+        .add_systems(OnEnter(Screen::Gameplay), (reset_lives, spawn_health_hud))
+        .add_systems(Update, update_health_hud.run_if(in_state(Screen::Gameplay)))
+        .add_observer(on_player_hit);
+}
+
+/// How many hits the player can take before the level restarts.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// All difficulties, in declaration order.
+    const ALL: [Self; 3] = [Self::Easy, Self::Normal, Self::Hard];
+
+    fn code(self) -> &'static str {
+        match self {
+            Self::Easy => "easy",
+            Self::Normal => "normal",
+            Self::Hard => "hard",
+        }
+    }
+
+    pub fn max_health(self) -> u32 {
+        match self {
+            Self::Easy => 5,
+            Self::Normal => 3,
+            Self::Hard => 1,
+        }
+    }
+
+    /// Localization key of the difficulty name.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Easy => "difficulty.easy",
+            Self::Normal => "difficulty.normal",
+            Self::Hard => "difficulty.hard",
+        }
+    }
+
+    pub fn easier(self) -> Self {
+        match self {
+            Self::Easy | Self::Normal => Self::Easy,
+            Self::Hard => Self::Normal,
+        }
+    }
+
+    pub fn harder(self) -> Self {
+        match self {
+            Self::Easy => Self::Normal,
+            Self::Normal | Self::Hard => Self::Hard,
+        }
+    }
+
+    fn load() -> Self {
+        let saved = persistence::load(SAVE_NAME).unwrap_or_default();
+
+        Self::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.code() == saved.trim())
+            .unwrap_or_default()
+    }
+
+    pub fn save(self) {
+        persistence::save(SAVE_NAME, self.code());
+    }
+}
+
+#[derive(Component)]
+pub struct Health {
+    pub current: u32,
+    pub max: u32,
+}
+
+impl Health {
+    pub fn full(max: u32) -> Self {
+        Self { current: max, max }
+    }
+}
+
+/// Remaining level restarts before the player is sent back a level.
+#[derive(Resource)]
+pub struct Lives(pub usize);
+
+impl Default for Lives {
+    fn default() -> Self {
+        Self(MAX_LIVES)
+    }
+}
+
+/// Triggered when something harmful at `source` touches the player.
+#[derive(Event)]
+pub struct PlayerHit {
+    pub source: Vec2,
+}
+
+/// Triggered when a [`PlayerHit`] costs the player health, unlike hits while invulnerable.
+#[derive(Event)]
+pub struct PlayerDamaged;
+
+/// Triggered when the player runs out of health, before the level restarts or the player is sent
+/// back a level.
+#[derive(Event)]
+pub struct PlayerDefeated;
+
+#[derive(Component)]
+struct HealthHudText;
+
+fn reset_lives(mut lives: ResMut<Lives>) {
+    *lives = Lives::default();
+}
+
+fn on_player_hit(
+    hit: On<PlayerHit>,
+    mut commands: Commands,
+    assets: Res<AllAssets>,
+    mut lives: ResMut<Lives>,
+    level_number: Res<LevelNumber>,
+    player: Single<(&mut Health, &mut HitReaction, &Transform), With<Player>>,
+) {
+    let (mut health, mut reaction, transform) = player.into_inner();
+    if reaction.is_invulnerable() {
+        return;
+    }
+
+    let knockback_direction = (transform.translation.truncate() - hit.source).normalize_or_zero();
+    reaction.start(knockback_direction);
+    commands.spawn(sound_effect_volume(assets.cat_hurt.clone(), 0.4));
+    commands.trigger(PlayerDamaged);
+
+    health.current = health.current.saturating_sub(1);
+    if health.current > 0 {
+        return;
+    }
+
+    commands.trigger(PlayerDefeated);
+    lives.0 = lives.0.saturating_sub(1);
+    if lives.0 > 0 {
+        commands.trigger(LevelRestart);
+        return;
+    }
+
+    *lives = Lives::default();
+    commands.trigger(NewLevel(level_number.0.saturating_sub(1)));
+}
+
+fn spawn_health_hud(mut commands: Commands, assets: Res<AllAssets>) {
+    commands.spawn((
+        Name::new("Health HUD"),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Vw(2.0),
+            top: Val::Vh(2.0),
+            ..default()
+        },
+        Text::default(),
+        TextFont {
+            font: assets.catex_fx_bold.clone(),
+            font_size: 28.0,
+            ..default()
+        },
+        TextColor(Color::linear_rgb(1.0, 1.0, 1.0)),
+        Pickable::IGNORE,
+        HealthHudText,
+        DespawnOnExit(Screen::Gameplay),
+    ));
+}
+
+fn update_health_hud(
+    lives: Res<Lives>,
+    health: Single<&Health, With<Player>>,
+    localizer: Localizer,
+    mut text: Single<&mut Text, With<HealthHudText>>,
+) {
+    text.0 = localizer.format("hud.health", &[&health.current, &health.max, &lives.0]);
+}
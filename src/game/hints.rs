@@ -0,0 +1,213 @@
+//! Hints shown to players who keep failing at the same spot.
+
+use std::{collections::HashMap, f32::consts::PI};
+
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    game::{
+        DestroyOnNewLevel, NewLevel,
+        area_hazard::HazardKind,
+        guide::{ChangeGuideText, GuidePriority},
+        health::PlayerDefeated,
+        level_data::level_data,
+        player::Player,
+        wall_block::{Key, WallBlock},
+    },
+    screens::Screen,
+};
+
+/// Deaths close to each other needed before a hint about that place is shown.
+const DEATHS_BEFORE_HINT: usize = 3;
+const DEATH_CLUSTER_RADIUS: f32 = 300.0;
+/// Deaths in a level, anywhere in it, before a general hint is shown.
+const DEATHS_BEFORE_GENERIC_HINT: usize = 5;
+/// How far from a hazard a death is still blamed on it.
+const HAZARD_CONTEXT_RADIUS: f32 = 400.0;
+const DOOR_WAIT_RADIUS: f32 = 500.0;
+const DOOR_WAIT_SECS: f32 = 8.0;
+const POINTER_DISTANCE: f32 = 90.0;
+const POINTER_SIZE: f32 = 16.0;
+const POINTER_Z: f32 = 160.0;
+const POINTER_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<FailureHistory>()
+        .add_systems(OnEnter(Screen::Gameplay), reset_failure_history)
+        .add_systems(
+            Update,
+            (show_pending_hint, watch_door_wait, update_key_pointer)
+                .run_if(in_state(Screen::Gameplay))
+                .in_set(PausableSystems),
+        )
+        .add_observer(record_death)
+        .add_observer(on_new_level);
+}
+
+/// Where the player failed in each level of this session, and the hints already given there.
+///
+/// Levels are kept apart, so running out of lives and being sent back a level doesn't forget the
+/// deaths in the level the player returns to.
+#[derive(Resource, Default)]
+struct FailureHistory {
+    level: usize,
+    levels: HashMap<usize, LevelFailures>,
+    door_wait: f32,
+}
+
+#[derive(Default)]
+struct LevelFailures {
+    deaths: Vec<Vec2>,
+    shown: Vec<&'static str>,
+    pending: Option<&'static str>,
+}
+
+impl FailureHistory {
+    fn current(&mut self) -> &mut LevelFailures {
+        self.levels.entry(self.level).or_default()
+    }
+
+    /// Queues the hint unless it was already shown in the current level.
+    fn give_hint(&mut self, key: &'static str) {
+        let failures = self.current();
+        if !failures.shown.contains(&key) {
+            failures.shown.push(key);
+            failures.pending = Some(key);
+        }
+    }
+}
+
+#[derive(Component)]
+struct KeyPointer;
+
+fn reset_failure_history(mut history: ResMut<FailureHistory>) {
+    *history = FailureHistory::default();
+}
+
+fn on_new_level(new_level: On<NewLevel>, mut history: ResMut<FailureHistory>) {
+    history.level = new_level.0;
+    history.door_wait = 0.0;
+}
+
+fn record_death(
+    _: On<PlayerDefeated>,
+    mut history: ResMut<FailureHistory>,
+    player: Single<&Transform, With<Player>>,
+) {
+    let position = player.translation.truncate();
+    let level = history.level;
+    let failures = history.current();
+    failures.deaths.push(position);
+
+    let nearby_deaths = failures
+        .deaths
+        .iter()
+        .filter(|death| death.distance(position) < DEATH_CLUSTER_RADIUS)
+        .count();
+
+    if nearby_deaths >= DEATHS_BEFORE_HINT {
+        history.give_hint(hint_for_death(level, position));
+    } else if failures.deaths.len() >= DEATHS_BEFORE_GENERIC_HINT {
+        history.give_hint("hint.generic");
+    }
+}
+
+/// Picks a hint about the hazard closest to where the player died.
+fn hint_for_death(level: usize, position: Vec2) -> &'static str {
+    let nearest_hazard = level_data(level)
+        .hazards
+        .iter()
+        .filter(|hazard| hazard.position.distance(position) < HAZARD_CONTEXT_RADIUS)
+        .min_by(|a, b| {
+            let a = a.position.distance_squared(position);
+            let b = b.position.distance_squared(position);
+            a.total_cmp(&b)
+        });
+
+    match nearest_hazard.map(|hazard| hazard.kind) {
+        Some(HazardKind::SweepingBeam { .. }) => "hint.beam",
+        Some(HazardKind::PulsingZone { .. }) => "hint.pulsing_zone",
+        Some(HazardKind::ClosingWalls { .. }) => "hint.closing_walls",
+        // Arrows start flying from the second level onwards.
+        None if level >= 1 => "hint.arrows",
+        None => "hint.generic",
+    }
+}
+
+fn show_pending_hint(mut commands: Commands, mut history: ResMut<FailureHistory>) {
+    // Only read until there is a hint, so the history isn't changed every frame.
+    let pending = history.levels.get(&history.level);
+    let Some(key) = pending.and_then(|failures| failures.pending) else {
+        return;
+    };
+
+    history.current().pending = None;
+    commands.trigger(ChangeGuideText::new(key, GuidePriority::Hint));
+}
+
+fn watch_door_wait(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut history: ResMut<FailureHistory>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    keys: Query<(), With<Key>>,
+    player: Single<&Transform, With<Player>>,
+    wall_blocks: Query<&Transform, With<WallBlock>>,
+) {
+    if keys.is_empty() || history.door_wait >= DOOR_WAIT_SECS {
+        return;
+    }
+
+    let position = player.translation.truncate();
+    let near_door = wall_blocks
+        .iter()
+        .any(|transform| transform.translation.truncate().distance(position) < DOOR_WAIT_RADIUS);
+    if !near_door {
+        return;
+    }
+
+    history.door_wait += time.delta_secs();
+    if history.door_wait < DOOR_WAIT_SECS {
+        return;
+    }
+
+    history.give_hint("hint.key");
+    commands.spawn((
+        Name::new("key pointer"),
+        KeyPointer,
+        DestroyOnNewLevel,
+        DespawnOnExit(Screen::Gameplay),
+        Mesh2d(meshes.add(Triangle2d::new(
+            Vec2::new(0.0, POINTER_SIZE),
+            Vec2::new(-POINTER_SIZE, -POINTER_SIZE),
+            Vec2::new(POINTER_SIZE, -POINTER_SIZE),
+        ))),
+        MeshMaterial2d(materials.add(POINTER_COLOR)),
+        Transform::from_translation(position.extend(POINTER_Z)),
+    ));
+}
+
+fn update_key_pointer(
+    mut commands: Commands,
+    key: Option<Single<&Transform, (With<Key>, Without<KeyPointer>)>>,
+    player: Single<&Transform, (With<Player>, Without<KeyPointer>)>,
+    pointers: Query<(Entity, &mut Transform), With<KeyPointer>>,
+) {
+    let Some(key) = key else {
+        for (entity, _) in pointers {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+
+    let position = player.translation.truncate();
+    let direction = (key.translation.truncate() - position).normalize_or_zero();
+
+    for (_, mut transform) in pointers {
+        let pointer_position = position + direction * POINTER_DISTANCE;
+        transform.translation = pointer_position.extend(POINTER_Z);
+        transform.rotation = Quat::from_rotation_z(direction.to_angle() - PI / 2.0);
+    }
+}
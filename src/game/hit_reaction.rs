@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    game::{
+        animation::{SpriteAnimation, update_animations},
+        player::Player,
+    },
+};
+
+const INVULNERABILITY_DURATION: f32 = 1.2;
+const BLINK_PERIOD: f32 = 0.1;
+const BLINK_ALPHA: f32 = 0.25;
+const KNOCKBACK_DURATION: f32 = 0.2;
+const KNOCKBACK_SPEED: f32 = 900.0;
+const HURT_DURATION: f32 = 0.3;
+const HURT_COLOR: Color = Color::srgb(1.0, 0.35, 0.35);
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        (tick_hit_reactions, apply_knockback)
+            .chain()
+            .in_set(PausableSystems),
+    )
+    .add_systems(
+        Update,
+        // The hurt frame replaces the frame of the walk animation.
+        update_hit_sprite
+            .after(update_animations)
+            .in_set(PausableSystems),
+    );
+}
+
+/// Feedback state of the last hit taken by the player.
+#[derive(Component)]
+pub struct HitReaction {
+    invulnerability: Timer,
+    knockback: Timer,
+    hurt: Timer,
+    pending_knockback: Option<Vec2>,
+    /// Whether the sprite blinks while invulnerable.
+    blinking: bool,
+}
+
+impl Default for HitReaction {
+    fn default() -> Self {
+        let finished = |duration| {
+            let mut timer = Timer::from_seconds(duration, TimerMode::Once);
+            timer.finish();
+            timer
+        };
+
+        Self {
+            invulnerability: finished(INVULNERABILITY_DURATION),
+            knockback: finished(KNOCKBACK_DURATION),
+            hurt: finished(HURT_DURATION),
+            pending_knockback: None,
+            blinking: false,
+        }
+    }
+}
+
+impl HitReaction {
+    /// Starts the reaction to a hit, knocking the player in `direction`.
+    pub fn start(&mut self, direction: Vec2) {
+        self.invulnerability
+            .set_duration(Duration::from_secs_f32(INVULNERABILITY_DURATION));
+        self.invulnerability.reset();
+        self.knockback.reset();
+        self.hurt.reset();
+        self.pending_knockback = Some(direction);
+        self.blinking = true;
+    }
+
+    /// Makes the player invulnerable for at least `duration` seconds, without blinking.
+    pub fn extend_invulnerability(&mut self, duration: f32) {
+        if self.invulnerability.remaining_secs() >= duration {
+            return;
+        }
+
+        self.invulnerability
+            .set_duration(Duration::from_secs_f32(duration));
+        self.invulnerability.reset();
+        self.blinking = false;
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        !self.invulnerability.is_finished()
+    }
+
+    pub fn is_knocked_back(&self) -> bool {
+        !self.knockback.is_finished()
+    }
+
+    pub fn is_hurt(&self) -> bool {
+        !self.hurt.is_finished()
+    }
+}
+
+fn tick_hit_reactions(time: Res<Time>, reactions: Query<&mut HitReaction>) {
+    for mut reaction in reactions {
+        reaction.invulnerability.tick(time.delta());
+        reaction.knockback.tick(time.delta());
+        reaction.hurt.tick(time.delta());
+    }
+}
+
+fn apply_knockback(player: Single<(Forces, &ComputedMass, &mut HitReaction), With<Player>>) {
+    let (mut forces, mass, mut reaction) = player.into_inner();
+
+    if let Some(direction) = reaction.pending_knockback.take() {
+        forces.apply_linear_impulse(direction * KNOCKBACK_SPEED * mass.value());
+    }
+}
+
+fn update_hit_sprite(player: Single<(&HitReaction, &SpriteAnimation, &mut Sprite), With<Player>>) {
+    let (reaction, animation, mut sprite) = player.into_inner();
+
+    // The hurt frame follows the walk frames, whose animation is paused while hurt.
+    let hurt_frame = animation.frame_count;
+    if let Some(atlas) = &mut sprite.texture_atlas {
+        if reaction.is_hurt() {
+            atlas.index = hurt_frame;
+        } else if atlas.index == hurt_frame {
+            atlas.index = 0;
+        }
+    }
+
+    let color = if reaction.is_hurt() {
+        HURT_COLOR
+    } else {
+        Color::WHITE
+    };
+    let blink_phase = (reaction.invulnerability.elapsed_secs() / BLINK_PERIOD) as u32;
+    let alpha = if reaction.is_invulnerable() && reaction.blinking && !blink_phase.is_multiple_of(2)
+    {
+        BLINK_ALPHA
+    } else {
+        1.0
+    };
+
+    sprite.color = color.with_alpha(alpha);
+}
@@ -0,0 +1,147 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    WAKE_UP_LEVEL,
+    area_hazard::{HazardKind, HazardPlacement},
+    camera::ZoomZone,
+    dialogue::DialoguePlacement,
+    movement::{MovementPreset, SurfacePlacement},
+};
+
+/// Static description of the content placed in a level.
+pub struct LevelData {
+    pub hazards: &'static [HazardPlacement],
+    pub collectibles: &'static [Vec2],
+    pub dialogues: &'static [DialoguePlacement],
+    pub movement: MovementPreset,
+    pub surfaces: &'static [SurfacePlacement],
+    /// Scale of the camera projection, where higher values show more of the level.
+    pub view_scale: f32,
+    pub zoom_zones: &'static [ZoomZone],
+}
+
+const LEVELS: [LevelData; WAKE_UP_LEVEL] = [
+    LevelData {
+        hazards: &[HazardPlacement {
+            position: Vec2::new(0.0, 2500.0),
+            kind: HazardKind::PulsingZone {
+                size: Vec2::new(600.0, 300.0),
+                safe_secs: 2.0,
+                active_secs: 1.5,
+            },
+        }],
+        collectibles: &[
+            Vec2::new(-450.0, 1200.0),
+            Vec2::new(450.0, 2000.0),
+            Vec2::new(-500.0, 3600.0),
+            Vec2::new(520.0, 5200.0),
+            Vec2::new(-480.0, 7000.0),
+        ],
+        dialogues: &[DialoguePlacement {
+            position: Vec2::new(0.0, 300.0),
+            id: "intro",
+        }],
+        movement: MovementPreset::Snappy,
+        surfaces: &[],
+        view_scale: 1.0,
+        zoom_zones: &[],
+    },
+    LevelData {
+        hazards: &[
+            HazardPlacement {
+                position: Vec2::new(-250.0, 1600.0),
+                kind: HazardKind::SweepingBeam {
+                    length: 500.0,
+                    angular_speed: 1.2,
+                },
+            },
+            HazardPlacement {
+                position: Vec2::new(250.0, 2100.0),
+                kind: HazardKind::SweepingBeam {
+                    length: 500.0,
+                    angular_speed: -1.2,
+                },
+            },
+        ],
+        collectibles: &[
+            Vec2::new(500.0, 1000.0),
+            Vec2::new(-520.0, 2600.0),
+            Vec2::new(450.0, 4100.0),
+            Vec2::new(-450.0, 5600.0),
+            Vec2::new(0.0, 7300.0),
+        ],
+        dialogues: &[DialoguePlacement {
+            position: Vec2::new(0.0, 2400.0),
+            id: "arrows",
+        }],
+        movement: MovementPreset::Snappy,
+        surfaces: &[SurfacePlacement {
+            position: Vec2::new(0.0, 3400.0),
+            size: Vec2::new(1200.0, 900.0),
+            movement: MovementPreset::Floaty,
+        }],
+        view_scale: 1.0,
+        // The arrow field.
+        zoom_zones: &[ZoomZone {
+            position: Vec2::new(0.0, 5200.0),
+            size: Vec2::new(1200.0, 5200.0),
+            view_scale: 1.3,
+        }],
+    },
+    LevelData {
+        hazards: &[
+            HazardPlacement {
+                position: Vec2::new(0.0, 1400.0),
+                kind: HazardKind::ClosingWalls {
+                    gap: 80.0,
+                    period: 4.0,
+                },
+            },
+            HazardPlacement {
+                position: Vec2::new(0.0, 2200.0),
+                kind: HazardKind::PulsingZone {
+                    size: Vec2::new(1200.0, 250.0),
+                    safe_secs: 2.5,
+                    active_secs: 1.0,
+                },
+            },
+        ],
+        collectibles: &[
+            Vec2::new(-500.0, 900.0),
+            Vec2::new(500.0, 1800.0),
+            Vec2::new(-450.0, 3300.0),
+            Vec2::new(480.0, 4800.0),
+            Vec2::new(-500.0, 6400.0),
+        ],
+        dialogues: &[DialoguePlacement {
+            position: Vec2::new(0.0, 300.0),
+            id: "bed_awakens",
+        }],
+        movement: MovementPreset::Normal,
+        surfaces: &[SurfacePlacement {
+            position: Vec2::new(0.0, 5600.0),
+            size: Vec2::new(1200.0, 1600.0),
+            movement: MovementPreset::Floaty,
+        }],
+        view_scale: 1.0,
+        zoom_zones: &[
+            // The locked door and the area in front of it.
+            ZoomZone {
+                position: Vec2::new(0.0, 7500.0),
+                size: Vec2::new(1200.0, 1000.0),
+                view_scale: 0.85,
+            },
+            // The toast field.
+            ZoomZone {
+                position: Vec2::new(0.0, 4800.0),
+                size: Vec2::new(1200.0, 4400.0),
+                view_scale: 1.25,
+            },
+        ],
+    },
+];
+
+/// Returns the data of the given level, or of the last level when out of range.
+pub fn level_data(level: usize) -> &'static LevelData {
+    &LEVELS[level.min(LEVELS.len() - 1)]
+}
@@ -3,34 +3,70 @@ use rand::{SeedableRng, rngs::SmallRng};
 
 use crate::{
     asset_tracking::LoadResource,
-    game::{glitch_effect::SpawnGlitchEffect, guide::ChangeGuideText},
+    game::{
+        dialogue::DialogueSet,
+        glitch_effect::SpawnGlitchEffect,
+        guide::{ChangeGuideText, GuidePriority},
+    },
     screens::Screen,
 };
 
 mod animation;
-mod arrows;
-mod environment;
+mod area_hazard;
+pub mod arrows;
+pub mod camera;
+pub mod collectible;
+mod collision;
+mod dash;
+mod dialogue;
+pub mod environment;
+pub mod ghost;
 pub mod glitch_effect;
 mod goal;
 mod guide;
-mod player;
+pub mod health;
+mod hints;
+mod hit_reaction;
+pub mod level_data;
+pub mod mouse_controls;
+pub mod movement;
+pub mod player;
+pub mod replay;
+pub mod score;
 mod wall_block;
 
-const WAKE_UP_LEVEL: usize = 3;
-const RANDOM_SOURCE_SEED: u64 = 0xDEAD_C0DE;
+pub const WAKE_UP_LEVEL: usize = 3;
+pub const RANDOM_SOURCE_SEED: u64 = 0xDEAD_C0DE;
 
 pub fn plugin(app: &mut App) {
     app.add_plugins((
         animation::plugin,
+        area_hazard::plugin,
+        camera::plugin,
         environment::plugin,
         goal::plugin,
         guide::plugin,
+        health::plugin,
+        hit_reaction::plugin,
         player::plugin,
+        score::plugin,
         glitch_effect::plugin,
-        arrows::plugin,
-        wall_block::plugin,
+        (
+            arrows::plugin,
+            collectible::plugin,
+            collision::plugin,
+            dash::plugin,
+            dialogue::plugin,
+            ghost::plugin,
+            hints::plugin,
+            mouse_controls::plugin,
+            movement::plugin,
+            replay::plugin,
+            wall_block::plugin,
+        ),
     ))
     .init_resource::<LevelNumber>()
+    .init_resource::<StartingLevel>()
     .insert_resource(RandomSource(SmallRng::seed_from_u64(RANDOM_SOURCE_SEED)))
     .add_systems(OnEnter(Screen::Gameplay), trigger_first_level)
     .add_observer(on_new_level)
@@ -56,6 +92,9 @@ pub struct AllAssets {
     pub steps: Handle<AudioSource>,
 
     pub catex_fx_bold: Handle<Font>,
+
+    #[dependency]
+    pub dialogues: Handle<DialogueSet>,
 }
 
 impl FromWorld for AllAssets {
@@ -81,6 +120,8 @@ impl FromWorld for AllAssets {
             steps: assets.load("audio/sound_effects/steps.wav"),
 
             catex_fx_bold: assets.load("fonts/CantedFX Bold.otf"),
+
+            dialogues: assets.load("dialogues/story.dialogue"),
         }
     }
 }
@@ -91,17 +132,26 @@ pub struct LevelRestart;
 #[derive(Event)]
 pub struct NewLevel(pub usize);
 
+/// Triggered when the player reaches the goal of the given level.
+#[derive(Event)]
+pub struct LevelCompleted(pub usize);
+
 #[derive(Resource, Default)]
 pub struct LevelNumber(pub usize);
 
+/// The level started when entering [`Screen::Gameplay`]. Reset to the first level once used.
+#[derive(Resource, Default)]
+pub struct StartingLevel(pub usize);
+
 #[derive(Resource)]
 pub struct RandomSource(pub SmallRng);
 
 #[derive(Component)]
 pub struct DestroyOnNewLevel;
 
-fn trigger_first_level(mut commands: Commands) {
-    commands.trigger(NewLevel(0));
+fn trigger_first_level(mut commands: Commands, mut starting_level: ResMut<StartingLevel>) {
+    commands.trigger(NewLevel(starting_level.0));
+    starting_level.0 = 0;
 }
 
 fn trigger_new_level_on_restart(
@@ -116,8 +166,10 @@ fn on_new_level(
     new_level: On<NewLevel>,
     mut commands: Commands,
     mut screen: ResMut<NextState<Screen>>,
+    mut level_number: ResMut<LevelNumber>,
     query: Query<Entity, With<DestroyOnNewLevel>>,
 ) {
+    level_number.0 = new_level.0;
     if new_level.0 == WAKE_UP_LEVEL {
         screen.set(Screen::Victory);
         return;
@@ -127,12 +179,12 @@ fn on_new_level(
         commands.trigger(SpawnGlitchEffect);
     }
 
-    let text = if new_level.0 >= 2 {
-        "You are a bed, your goal is to reach your cat."
+    let key = if new_level.0 >= 2 {
+        "guide.bed_goal"
     } else {
-        "You are a cat, your goal is to reach your bed."
+        "guide.cat_goal"
     };
-    commands.trigger(ChangeGuideText(String::from(text)));
+    commands.trigger(ChangeGuideText::new(key, GuidePriority::Story));
 
     for entity in query {
         commands.entity(entity).despawn();
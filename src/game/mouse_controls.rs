@@ -0,0 +1,272 @@
+//! Mouse controls, as an alternative to moving with the keyboard.
+//!
+//! Depending on [`MouseControls`], the player either walks towards the cursor while the left
+//! mouse button is held, or finds a path to a clicked point. Both feed the movement of
+//! [`ActionState`], so the player turns and walks the same way as with any other input.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use avian2d::prelude::*;
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    game::{NewLevel, environment::ROAD_SIZE, player::Player, wall_block::WallBlock},
+    input::{ActionState, ReadActions},
+    menus::Menu,
+    persistence,
+    screens::Screen,
+};
+
+const SAVE_NAME: &str = "mouse_controls";
+
+/// Distance from the cursor at which steering stops, so the player doesn't circle around it.
+const STEER_STOP_RADIUS: f32 = 24.0;
+const PATH_CELL_SIZE: f32 = 64.0;
+/// Space kept between a path and the obstacles around it.
+const PATH_CLEARANCE: f32 = 40.0;
+const WAYPOINT_RADIUS: f32 = 24.0;
+const STRAIGHT_STEP_COST: u32 = 10;
+const DIAGONAL_STEP_COST: u32 = 14;
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(MouseControls::load())
+        .init_resource::<MousePath>()
+        .add_systems(OnEnter(Screen::Gameplay), clear_path)
+        .add_systems(
+            PreUpdate,
+            read_mouse_controls
+                .after(ReadActions)
+                .run_if(in_state(Screen::Gameplay).and(in_state(Menu::None))),
+        )
+        .add_observer(clear_path_on_new_level);
+}
+
+/// How the player can be moved with the mouse.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MouseControls {
+    #[default]
+    Off,
+    HoldToSteer,
+    ClickToMove,
+}
+
+impl MouseControls {
+    /// All control schemes, in declaration order.
+    const ALL: [Self; 3] = [Self::Off, Self::HoldToSteer, Self::ClickToMove];
+
+    fn code(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::HoldToSteer => "hold_to_steer",
+            Self::ClickToMove => "click_to_move",
+        }
+    }
+
+    /// Localization key of the control scheme name.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "mouse_controls.off",
+            Self::HoldToSteer => "mouse_controls.hold_to_steer",
+            Self::ClickToMove => "mouse_controls.click_to_move",
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        Self::ALL[(self as usize + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    pub fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    fn load() -> Self {
+        let saved = persistence::load(SAVE_NAME).unwrap_or_default();
+
+        Self::ALL
+            .into_iter()
+            .find(|controls| controls.code() == saved.trim())
+            .unwrap_or_default()
+    }
+
+    pub fn save(self) {
+        persistence::save(SAVE_NAME, self.code());
+    }
+}
+
+/// Waypoints left on the way to the clicked point, the next one last.
+#[derive(Resource, Default)]
+pub(super) struct MousePath(Vec<Vec2>);
+
+fn clear_path(mut path: ResMut<MousePath>) {
+    path.0.clear();
+}
+
+fn clear_path_on_new_level(_: On<NewLevel>, mut path: ResMut<MousePath>) {
+    path.0.clear();
+}
+
+pub(super) fn read_mouse_controls(
+    controls: Res<MouseControls>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<Camera2d>>,
+    player: Single<&Transform, With<Player>>,
+    obstacles: Query<&ColliderAabb, With<WallBlock>>,
+    mut path: ResMut<MousePath>,
+    mut actions: ResMut<ActionState>,
+) {
+    let (camera, camera_transform) = *camera;
+    let cursor = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok());
+    let position = player.translation.truncate();
+
+    match *controls {
+        MouseControls::Off => path.0.clear(),
+        MouseControls::HoldToSteer => {
+            path.0.clear();
+
+            if buttons.pressed(MouseButton::Left)
+                && let Some(cursor) = cursor
+                && cursor.distance(position) > STEER_STOP_RADIUS
+            {
+                actions.add_movement((cursor - position).normalize());
+            }
+        }
+        MouseControls::ClickToMove => {
+            // Moving with any other input cancels the walk.
+            if actions.movement() != Vec2::ZERO {
+                path.0.clear();
+            }
+
+            if buttons.just_pressed(MouseButton::Left)
+                && let Some(cursor) = cursor
+            {
+                let obstacles: Vec<_> = obstacles
+                    .iter()
+                    .map(|aabb| Rect::from_corners(aabb.min, aabb.max).inflate(PATH_CLEARANCE))
+                    .collect();
+                path.0 = find_path(position, cursor, &obstacles);
+            }
+
+            while path
+                .0
+                .last()
+                .is_some_and(|waypoint| waypoint.distance(position) < WAYPOINT_RADIUS)
+            {
+                path.0.pop();
+            }
+
+            if let Some(waypoint) = path.0.last() {
+                actions.add_movement((*waypoint - position).normalize_or_zero());
+            }
+        }
+    }
+}
+
+/// Finds a path around `obstacles` with A* on a grid covering the road. Returns the waypoints
+/// in reverse order. When the target can't be reached, the path leads as close to it as possible.
+fn find_path(start: Vec2, target: Vec2, obstacles: &[Rect]) -> Vec<Vec2> {
+    let road = Rect::new(-ROAD_SIZE.x / 2.0, 0.0, ROAD_SIZE.x / 2.0, ROAD_SIZE.y);
+    let size = (road.size() / PATH_CELL_SIZE).ceil().as_ivec2();
+
+    let cell_of = |point: Vec2| {
+        ((point - road.min) / PATH_CELL_SIZE)
+            .floor()
+            .as_ivec2()
+            .clamp(IVec2::ZERO, size - 1)
+    };
+    let center_of = |cell: IVec2| road.min + (cell.as_vec2() + 0.5) * PATH_CELL_SIZE;
+    let walkable = |cell: IVec2| {
+        cell.cmpge(IVec2::ZERO).all()
+            && cell.cmplt(size).all()
+            && !obstacles
+                .iter()
+                .any(|obstacle| obstacle.contains(center_of(cell)))
+    };
+
+    let start_cell = cell_of(start);
+    let target_cell = cell_of(target);
+    let estimate = |cell: IVec2| {
+        let delta = (target_cell - cell).abs();
+        let diagonal = delta.min_element() as u32;
+        let straight = delta.max_element() as u32 - diagonal;
+        diagonal * DIAGONAL_STEP_COST + straight * STRAIGHT_STEP_COST
+    };
+
+    let mut open = BinaryHeap::from([Reverse((estimate(start_cell), start_cell.x, start_cell.y))]);
+    let mut costs = HashMap::from([(start_cell, 0)]);
+    let mut came_from = HashMap::new();
+    let mut closest = start_cell;
+
+    while let Some(Reverse((_, x, y))) = open.pop() {
+        let cell = IVec2::new(x, y);
+        if estimate(cell) < estimate(closest) {
+            closest = cell;
+        }
+        if cell == target_cell {
+            break;
+        }
+
+        let cost = costs[&cell];
+        for step in [
+            IVec2::X,
+            IVec2::NEG_X,
+            IVec2::Y,
+            IVec2::NEG_Y,
+            IVec2::ONE,
+            IVec2::NEG_ONE,
+            IVec2::new(1, -1),
+            IVec2::new(-1, 1),
+        ] {
+            let neighbor = cell + step;
+            let diagonal = step.x != 0 && step.y != 0;
+            // Diagonal steps must not cut the corner of an obstacle.
+            if !walkable(neighbor)
+                || diagonal && !(walkable(cell + step.with_y(0)) && walkable(cell + step.with_x(0)))
+            {
+                continue;
+            }
+
+            let step_cost = if diagonal {
+                DIAGONAL_STEP_COST
+            } else {
+                STRAIGHT_STEP_COST
+            };
+            let neighbor_cost = cost + step_cost;
+            if costs
+                .get(&neighbor)
+                .is_some_and(|&known| known <= neighbor_cost)
+            {
+                continue;
+            }
+
+            costs.insert(neighbor, neighbor_cost);
+            came_from.insert(neighbor, cell);
+            open.push(Reverse((
+                neighbor_cost + estimate(neighbor),
+                neighbor.x,
+                neighbor.y,
+            )));
+        }
+    }
+
+    let end = if closest == target_cell && road.contains(target) {
+        target
+    } else {
+        center_of(closest)
+    };
+    let mut waypoints = vec![end];
+    let mut cell = closest;
+    while let Some(&previous) = came_from.get(&cell) {
+        if previous != start_cell {
+            waypoints.push(center_of(previous));
+        }
+        cell = previous;
+    }
+
+    waypoints
+}
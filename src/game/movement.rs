@@ -0,0 +1,174 @@
+//! How quickly the player speeds up, slows down and turns.
+//!
+//! Each level has a [`MovementPreset`], and [`Surface`]s placed in a level can override it in
+//! their area.
+
+use bevy::prelude::*;
+
+use crate::{
+    game::{DestroyOnNewLevel, NewLevel, level_data::level_data},
+    screens::Screen,
+    tuning::{Tuning, init_tuning},
+};
+
+const SURFACE_Z: f32 = -60.0;
+const SURFACE_COLOR: Color = Color::srgba(0.75, 0.8, 1.0, 0.25);
+
+pub(super) fn plugin(app: &mut App) {
+    init_tuning::<MovementTuning>(app);
+
+    app.add_observer(spawn_surfaces);
+}
+
+/// A kind of movement, whose parameters are read from [`MovementTuning`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MovementPreset {
+    /// Reaches full speed and stops almost instantly.
+    Snappy,
+    Normal,
+    /// Slow to get going and slides for a while after letting go, like walking in a dream.
+    Floaty,
+}
+
+/// Parameters of the player movement.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MovementModel {
+    pub max_speed: f32,
+    /// Speed gained per second while moving.
+    pub acceleration: f32,
+    /// Speed lost per second after letting go.
+    pub deceleration: f32,
+    /// Multiplier of the acceleration when turning around, blended in for smaller turns.
+    pub turn_responsiveness: f32,
+}
+
+/// Tunable values of each [`MovementPreset`], see [`crate::tuning`].
+#[derive(Resource, Reflect, Clone, Debug)]
+#[reflect(Resource)]
+pub struct MovementTuning {
+    pub snappy_max_speed: f32,
+    pub snappy_acceleration: f32,
+    pub snappy_deceleration: f32,
+    pub snappy_turn_responsiveness: f32,
+    pub normal_max_speed: f32,
+    pub normal_acceleration: f32,
+    pub normal_deceleration: f32,
+    pub normal_turn_responsiveness: f32,
+    pub floaty_max_speed: f32,
+    pub floaty_acceleration: f32,
+    pub floaty_deceleration: f32,
+    pub floaty_turn_responsiveness: f32,
+}
+
+impl Default for MovementTuning {
+    fn default() -> Self {
+        Self {
+            snappy_max_speed: 600.0,
+            snappy_acceleration: 8000.0,
+            snappy_deceleration: 10000.0,
+            snappy_turn_responsiveness: 2.0,
+            normal_max_speed: 600.0,
+            normal_acceleration: 3000.0,
+            normal_deceleration: 4000.0,
+            normal_turn_responsiveness: 2.0,
+            floaty_max_speed: 520.0,
+            floaty_acceleration: 900.0,
+            floaty_deceleration: 450.0,
+            floaty_turn_responsiveness: 0.6,
+        }
+    }
+}
+
+impl Tuning for MovementTuning {
+    const SECTION: &'static str = "movement";
+}
+
+impl MovementTuning {
+    pub fn model(&self, preset: MovementPreset) -> MovementModel {
+        match preset {
+            MovementPreset::Snappy => MovementModel {
+                max_speed: self.snappy_max_speed,
+                acceleration: self.snappy_acceleration,
+                deceleration: self.snappy_deceleration,
+                turn_responsiveness: self.snappy_turn_responsiveness,
+            },
+            MovementPreset::Normal => MovementModel {
+                max_speed: self.normal_max_speed,
+                acceleration: self.normal_acceleration,
+                deceleration: self.normal_deceleration,
+                turn_responsiveness: self.normal_turn_responsiveness,
+            },
+            MovementPreset::Floaty => MovementModel {
+                max_speed: self.floaty_max_speed,
+                acceleration: self.floaty_acceleration,
+                deceleration: self.floaty_deceleration,
+                turn_responsiveness: self.floaty_turn_responsiveness,
+            },
+        }
+    }
+}
+
+impl MovementModel {
+    /// Moves `velocity` towards the `target` velocity over `delta_secs`.
+    pub fn update_velocity(&self, velocity: Vec2, target: Vec2, delta_secs: f32) -> Vec2 {
+        let rate = if target == Vec2::ZERO {
+            self.deceleration
+        } else {
+            // `0.0` when keeping the direction, `1.0` when turning around.
+            let turning = (1.0 - velocity.normalize_or_zero().dot(target.normalize())) / 2.0;
+            self.acceleration * 1.0.lerp(self.turn_responsiveness, turning)
+        };
+
+        velocity.move_towards(target, rate * delta_secs)
+    }
+}
+
+/// An area of a level with its own movement.
+#[derive(Clone, Copy, Debug)]
+pub struct SurfacePlacement {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub movement: MovementPreset,
+}
+
+#[derive(Component)]
+pub struct Surface {
+    area: Rect,
+    movement: MovementPreset,
+}
+
+/// Returns the movement at `position`, which is the level's one outside of all surfaces.
+pub fn movement_preset<'a>(
+    level: usize,
+    position: Vec2,
+    surfaces: impl IntoIterator<Item = &'a Surface>,
+) -> MovementPreset {
+    surfaces
+        .into_iter()
+        .find(|surface| surface.area.contains(position))
+        .map_or(level_data(level).movement, |surface| surface.movement)
+}
+
+fn spawn_surfaces(
+    new_level: On<NewLevel>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let material = materials.add(SURFACE_COLOR);
+
+    for placement in level_data(new_level.0).surfaces {
+        commands.spawn((
+            Name::new("surface"),
+            Surface {
+                area: Rect::from_center_size(placement.position, placement.size),
+                movement: placement.movement,
+            },
+            Mesh2d(meshes.add(Rectangle::from_size(placement.size))),
+            MeshMaterial2d(material.clone()),
+            Transform::from_translation(placement.position.extend(SURFACE_Z)),
+            DestroyOnNewLevel,
+            DespawnOnExit(Screen::Gameplay),
+        ));
+    }
+}
@@ -1,57 +1,124 @@
 use avian2d::prelude::*;
 use bevy::prelude::*;
 use rand::Rng;
-use std::f32::consts::PI;
+use std::{f32::consts::PI, time::Duration};
 
 use crate::{
-    PausableSystems,
+    AppSystems, PausableSystems,
     audio::sound_effect_complex,
-    game::{AllAssets, DestroyOnNewLevel, NewLevel, RandomSource, animation::SpriteAnimation},
+    game::{
+        AllAssets, DestroyOnNewLevel, LevelNumber, NewLevel, RandomSource,
+        animation::SpriteAnimation,
+        collision::GameLayer,
+        dash::Dash,
+        health::{Difficulty, Health},
+        hit_reaction::HitReaction,
+        mouse_controls::read_mouse_controls,
+        movement::{MovementTuning, Surface, movement_preset},
+    },
+    input::{Action, ActionState, ReadActions},
     screens::Screen,
+    tuning::{Tuning, init_tuning},
 };
 
-const PLAYER_SCALE: f32 = 2.0;
-const PLAYER_Z: f32 = 100.0;
-const PLAYER_MOVEMENT_SPEED: f32 = 600.0;
-const PLAYER_ROTATION_SPEED: f32 = 8.0;
-const PLAYER_WALK_SOUND_PERIOD: f32 = 0.25;
+pub const PLAYER_Z: f32 = 100.0;
 
 pub fn plugin(app: &mut App) {
+    init_tuning::<PlayerTuning>(app);
+
     app.add_observer(spawn_player)
-        .add_systems(PreUpdate, read_keyboard_input)
         .add_systems(
-            Update,
+            PreUpdate,
+            read_input.after(ReadActions).after(read_mouse_controls),
+        )
+        .add_systems(
+            FixedUpdate,
             (
                 apply_linear_velocity,
                 apply_angular_velocity,
-                update_animation,
-                play_walking_sound,
+                (update_noise, play_walking_sound).chain(),
             )
+                .in_set(AppSystems::Update)
                 .in_set(PausableSystems),
         )
         .add_systems(
-            PostUpdate,
-            update_follow_camera
-                .run_if(in_state(Screen::Gameplay))
+            Update,
+            update_animation
+                .in_set(AppSystems::Update)
                 .in_set(PausableSystems),
         );
 }
 
+/// Tunable values of the player, see [`crate::tuning`].
+#[derive(Resource, Reflect, Clone, Debug)]
+#[reflect(Resource)]
+pub struct PlayerTuning {
+    pub scale: f32,
+    pub rotation_speed: f32,
+    /// Seconds between steps while walking.
+    pub walk_sound_period: f32,
+    /// Multiplier of the movement speed while sneaking.
+    pub sneak_speed_factor: f32,
+    /// Seconds between steps while sneaking.
+    pub sneak_walk_sound_period: f32,
+    /// Noise made while sneaking, compared to walking.
+    pub sneak_noise: f32,
+}
+
+impl Default for PlayerTuning {
+    fn default() -> Self {
+        Self {
+            scale: 2.0,
+            rotation_speed: 8.0,
+            walk_sound_period: 0.25,
+            sneak_speed_factor: 0.4,
+            sneak_walk_sound_period: 0.5,
+            sneak_noise: 0.25,
+        }
+    }
+}
+
+impl Tuning for PlayerTuning {
+    const SECTION: &'static str = "player";
+}
+
 #[derive(Component)]
 pub struct Player {
     movement_direction: Vec2,
     walking_sound_timer: Timer,
+    sneaking: bool,
     pub disable_movement: bool,
 }
 
+/// How loud the player currently is, from `0.0` (silent) to `1.0` (walking normally).
+#[derive(Component, Default)]
+pub struct Noise(pub f32);
+
+impl Player {
+    pub fn movement_direction(&self) -> Vec2 {
+        self.movement_direction
+    }
+
+    pub fn is_sneaking(&self) -> bool {
+        self.sneaking
+    }
+
+    /// Replaces the input read this tick with a recorded one.
+    pub fn replay_input(&mut self, movement_direction: Vec2, sneaking: bool) {
+        self.movement_direction = movement_direction;
+        self.sneaking = sneaking;
+    }
+}
+
 impl Default for Player {
     fn default() -> Self {
         Self {
             movement_direction: Vec2::ZERO,
             walking_sound_timer: Timer::from_seconds(
-                PLAYER_WALK_SOUND_PERIOD,
+                PlayerTuning::default().walk_sound_period,
                 TimerMode::Repeating,
             ),
+            sneaking: false,
             disable_movement: false,
         }
     }
@@ -61,73 +128,114 @@ fn spawn_player(
     new_level: On<NewLevel>,
     mut commands: Commands,
     assets: Res<AllAssets>,
+    difficulty: Res<Difficulty>,
+    tuning: Res<PlayerTuning>,
     mut layouts: ResMut<Assets<TextureAtlasLayout>>,
 ) {
-    let layout = TextureAtlasLayout::from_grid(UVec2::splat(64), 4, 1, None, None);
-    let layout = layouts.add(layout);
-
-    let handle = if new_level.0 >= 2 {
-        assets.cat_bed.clone()
-    } else {
-        assets.cat.clone()
-    };
     let collider = if new_level.0 >= 2 {
         Collider::circle(20.0)
     } else {
         Collider::capsule(7.5, 35.0)
     };
-    let frame_count = if new_level.0 >= 2 { 1 } else { 4 };
 
     commands.spawn((
         Name::new("player"),
         Player::default(),
-        Sprite::from_atlas_image(handle, TextureAtlas { layout, index: 0 }),
-        SpriteAnimation::new(6.0, true, frame_count),
+        Noise::default(),
+        Health::full(difficulty.max_health()),
+        HitReaction::default(),
+        Dash::default(),
+        player_sprite(new_level.0, &assets, &mut layouts),
         RigidBody::Dynamic,
         collider,
-        Transform::from_xyz(0.0, 300.0, PLAYER_Z).with_scale(Vec3::splat(PLAYER_SCALE)),
+        CollisionLayers::new(
+            GameLayer::Player,
+            [GameLayer::World, GameLayer::Hazard, GameLayer::Trigger],
+        ),
+        Transform::from_xyz(0.0, 300.0, PLAYER_Z).with_scale(Vec3::splat(tuning.scale)),
         DestroyOnNewLevel,
         DespawnOnExit(Screen::Gameplay),
     ));
 }
 
-fn update_follow_camera(
-    player: Single<&Transform, (With<Player>, Without<Camera2d>)>,
-    mut camera: Single<&mut Transform, (With<Camera2d>, Without<Player>)>,
-) {
-    camera.translation = player.translation.with_z(camera.translation.z);
+/// The animated sprite of the player in the given level.
+pub fn player_sprite(
+    level: usize,
+    assets: &AllAssets,
+    layouts: &mut Assets<TextureAtlasLayout>,
+) -> (Sprite, SpriteAnimation) {
+    let handle = if level >= 2 {
+        assets.cat_bed.clone()
+    } else {
+        assets.cat.clone()
+    };
+    let frame_count = if level >= 2 { 1 } else { 4 };
+
+    // The walk frames are followed by the hurt frame.
+    let layout =
+        TextureAtlasLayout::from_grid(UVec2::splat(64), frame_count as u32 + 1, 1, None, None);
+    let layout = layouts.add(layout);
+
+    (
+        Sprite::from_atlas_image(handle, TextureAtlas { layout, index: 0 }),
+        SpriteAnimation::new(6.0, true, frame_count),
+    )
 }
 
-fn read_keyboard_input(keyboard: Res<ButtonInput<KeyCode>>, mut player: Single<&mut Player>) {
+fn read_input(actions: Res<ActionState>, mut player: Single<&mut Player>) {
     if player.disable_movement {
         player.movement_direction = Vec2::ZERO;
+        player.sneaking = false;
         return;
     }
 
-    let mut direction = Vec2::ZERO;
-    if keyboard.pressed(KeyCode::KeyW) {
-        direction.y += 1.0;
-    }
-    if keyboard.pressed(KeyCode::KeyA) {
-        direction.x -= 1.0;
-    }
-    if keyboard.pressed(KeyCode::KeyS) {
-        direction.y -= 1.0;
-    }
-    if keyboard.pressed(KeyCode::KeyD) {
-        direction.x += 1.0;
-    }
-
-    player.movement_direction = direction.normalize_or_zero();
+    player.sneaking = actions.pressed(Action::Sneak);
+    player.movement_direction = actions.movement();
 }
 
-fn apply_linear_velocity(player: Single<(&Player, &mut LinearVelocity)>) {
-    let (player, mut velocity) = player.into_inner();
+fn apply_linear_velocity(
+    time: Res<Time>,
+    tuning: Res<PlayerTuning>,
+    movement_tuning: Res<MovementTuning>,
+    level: Res<LevelNumber>,
+    surfaces: Query<&Surface>,
+    player: Single<(
+        &Player,
+        &HitReaction,
+        &Dash,
+        &Transform,
+        &mut LinearVelocity,
+    )>,
+) {
+    let (player, reaction, dash, transform, mut velocity) = player.into_inner();
 
-    velocity.0 = player.movement_direction * PLAYER_MOVEMENT_SPEED;
+    if reaction.is_knocked_back() {
+        return;
+    }
+
+    if dash.is_dashing() {
+        velocity.0 = dash.velocity();
+        return;
+    }
+
+    let preset = movement_preset(level.0, transform.translation.truncate(), surfaces);
+    let model = movement_tuning.model(preset);
+    let speed = if player.sneaking {
+        model.max_speed * tuning.sneak_speed_factor
+    } else {
+        model.max_speed
+    };
+    velocity.0 = model.update_velocity(
+        velocity.0,
+        player.movement_direction * speed,
+        time.delta_secs(),
+    );
 }
 
-fn apply_angular_velocity(player: Single<(&Player, &Transform, &mut AngularVelocity)>) {
+fn apply_angular_velocity(
+    tuning: Res<PlayerTuning>,
+    player: Single<(&Player, &Transform, &mut AngularVelocity)>,
+) {
     let (player, transform, mut velocity) = player.into_inner();
 
     if player.movement_direction == Vec2::ZERO {
@@ -139,32 +247,56 @@ fn apply_angular_velocity(player: Single<(&Player, &Transform, &mut AngularVeloc
     let current_rotation = transform.rotation.to_euler(EulerRot::XYZ).2;
 
     let delta = (target_rotation - current_rotation + PI).rem_euclid(2.0 * PI) - PI;
-    velocity.0 = delta * PLAYER_ROTATION_SPEED;
+    velocity.0 = delta * tuning.rotation_speed;
+}
+
+fn update_animation(player: Single<(&Player, &HitReaction, &mut SpriteAnimation)>) {
+    let (player, reaction, mut animation) = player.into_inner();
+
+    animation.paused = player.movement_direction == Vec2::ZERO || reaction.is_hurt();
 }
 
-fn update_animation(player: Single<(&Player, &mut SpriteAnimation)>) {
-    let (player, mut animation) = player.into_inner();
+fn update_noise(tuning: Res<PlayerTuning>, player: Single<(&Player, &mut Noise)>) {
+    let (player, mut noise) = player.into_inner();
 
-    animation.paused = player.movement_direction == Vec2::ZERO;
+    // A slightly tilted stick walks slowly, and so more quietly.
+    let tilt = player.movement_direction.length();
+    noise.0 = if player.sneaking {
+        tuning.sneak_noise * tilt
+    } else {
+        tilt
+    };
 }
 
 fn play_walking_sound(
     mut commands: Commands,
     assets: Res<AllAssets>,
     time: Res<Time>,
+    tuning: Res<PlayerTuning>,
     mut random_source: ResMut<RandomSource>,
-    mut player: Single<&mut Player>,
+    player: Single<(&mut Player, &Noise)>,
 ) {
+    let (mut player, noise) = player.into_inner();
     if player.movement_direction == Vec2::ZERO {
         return;
     }
 
+    let period = if player.sneaking {
+        tuning.sneak_walk_sound_period
+    } else {
+        tuning.walk_sound_period
+    };
+    let period = Duration::from_secs_f32(period);
+    if player.walking_sound_timer.duration() != period {
+        player.walking_sound_timer.set_duration(period);
+    }
+
     player.walking_sound_timer.tick(time.delta());
     if !player.walking_sound_timer.just_finished() {
         return;
     }
 
-    let volume = random_source.0.random_range(0.12..=0.3);
+    let volume = random_source.0.random_range(0.12..=0.3) * noise.0;
     let speed = random_source.0.random_range(0.25..=2.0);
 
     let handle = assets.steps.clone();
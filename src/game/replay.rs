@@ -0,0 +1,523 @@
+//! Recording of the player input, and its playback.
+//!
+//! Every gameplay session is recorded tick by tick into the `replay` save, together with the
+//! random seed and the level it started in. Playing the save back feeds the recorded input to the
+//! player in place of live input. The simulation runs on a fixed timestep, so the same input
+//! reproduces the session exactly at any frame rate.
+//!
+//! Each tick also records where the player was, which the ghost of [`super::ghost`] follows.
+//!
+//! Levels picked in the level select during a session are recorded with the tick they were
+//! picked before, and played back at the same tick.
+
+use std::{
+    fmt::Write,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use bevy::{prelude::*, tasks::IoTaskPool};
+use rand::{SeedableRng, rngs::SmallRng};
+
+use crate::{
+    AppSystems, PausableSystems,
+    game::{
+        LevelRestart, NewLevel, RANDOM_SOURCE_SEED, RandomSource, StartingLevel, dash::Dash,
+        player::Player,
+    },
+    menus::Menu,
+    persistence,
+    screens::Screen,
+};
+
+const SAVE_NAME: &str = "replay";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Recording>()
+        .init_resource::<ChosenLevel>()
+        .init_resource::<RecordingSaves>()
+        .add_systems(
+            OnEnter(Screen::Gameplay),
+            start_recording.before(super::trigger_first_level),
+        )
+        .add_systems(
+            OnExit(Screen::Gameplay),
+            (save_recording, stop_playback).chain(),
+        )
+        .add_systems(
+            FixedUpdate,
+            play_back_chosen_level
+                .run_if(resource_exists::<Playback>)
+                .run_if(in_state(Screen::Gameplay))
+                .before(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                play_back_input.run_if(resource_exists::<Playback>),
+                record_input,
+            )
+                .chain()
+                .run_if(in_state(Screen::Gameplay))
+                .in_set(AppSystems::RecordInput)
+                .in_set(PausableSystems),
+        )
+        .add_observer(save_recording_on_restart)
+        .add_observer(play_replay)
+        .add_observer(choose_level);
+}
+
+/// Plays back the replay saved by the last gameplay session.
+#[derive(Event)]
+pub struct PlayReplay;
+
+/// Switches to the given level in the middle of a gameplay session, e.g. from the level select.
+#[derive(Event)]
+pub struct ChooseLevel(pub usize);
+
+/// A recorded gameplay session, or a part of one.
+#[derive(Default, Clone)]
+pub struct Replay {
+    seed: u64,
+    /// The level the recording starts in.
+    pub level: usize,
+    pub frames: Vec<ReplayFrame>,
+}
+
+/// Input of the player during a single tick, and where the player was at its start.
+#[derive(Clone, Copy)]
+pub struct ReplayFrame {
+    pub movement: Vec2,
+    pub sneaking: bool,
+    pub dash: bool,
+    pub position: Vec2,
+    /// Rotation of the player around the z axis, in radians.
+    pub rotation: f32,
+    /// Level chosen in the level select before this tick.
+    pub chosen_level: Option<usize>,
+}
+
+impl Replay {
+    pub fn load(save_name: &str) -> Option<Self> {
+        persistence::load(save_name).and_then(|contents| Self::parse(&contents))
+    }
+
+    pub fn save(&self, save_name: &str) {
+        persistence::save(save_name, &self.serialize());
+    }
+
+    /// Parses a replay saved by [`Replay::serialize`].
+    fn parse(contents: &str) -> Option<Self> {
+        let mut lines = contents.lines();
+        let seed = lines.next()?.strip_prefix("seed ")?.parse().ok()?;
+        let level = lines.next()?.strip_prefix("level ")?.parse().ok()?;
+        let frames = lines.map(ReplayFrame::parse).collect::<Option<_>>()?;
+
+        Some(Self {
+            seed,
+            level,
+            frames,
+        })
+    }
+
+    /// Writes the seed and the level, followed by one line per tick.
+    fn serialize(&self) -> String {
+        let mut contents = format!("seed {}\nlevel {}\n", self.seed, self.level);
+        for frame in &self.frames {
+            let _ = writeln!(
+                contents,
+                "{} {} {} {} {} {} {} {}",
+                frame.movement.x,
+                frame.movement.y,
+                u8::from(frame.sneaking),
+                u8::from(frame.dash),
+                frame.position.x,
+                frame.position.y,
+                frame.rotation,
+                frame
+                    .chosen_level
+                    .map_or_else(|| "-".to_string(), |level| level.to_string()),
+            );
+        }
+        contents
+    }
+}
+
+impl ReplayFrame {
+    fn parse(line: &str) -> Option<Self> {
+        let mut values = line.split_whitespace();
+        let movement = Vec2::new(values.next()?.parse().ok()?, values.next()?.parse().ok()?);
+        let sneaking = values.next()? == "1";
+        let dash = values.next()? == "1";
+        let position = Vec2::new(values.next()?.parse().ok()?, values.next()?.parse().ok()?);
+        let rotation = values.next()?.parse().ok()?;
+        let chosen_level = match values.next()? {
+            "-" => None,
+            level => Some(level.parse().ok()?),
+        };
+        if values.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            movement,
+            sneaking,
+            dash,
+            position,
+            rotation,
+            chosen_level,
+        })
+    }
+}
+
+/// The replay of the current gameplay session.
+#[derive(Resource, Default)]
+pub struct Recording(Replay);
+
+impl Recording {
+    /// Number of ticks recorded so far.
+    pub fn ticks(&self) -> usize {
+        self.0.frames.len()
+    }
+
+    /// Copies the ticks recorded since `start` into a replay starting in `level`.
+    pub fn since(&self, start: usize, level: usize) -> Replay {
+        Replay {
+            seed: self.0.seed,
+            level,
+            frames: self.0.frames.get(start..).unwrap_or_default().to_vec(),
+        }
+    }
+}
+
+/// Saves of the recording written on the IO task pool, as serializing a long session would stall
+/// the frame.
+#[derive(Resource, Default)]
+struct RecordingSaves {
+    /// Number of saves started.
+    started: u64,
+    /// Number of the newest save written, so that an older save finishing late is skipped.
+    written: Arc<Mutex<u64>>,
+}
+
+impl RecordingSaves {
+    fn save(&mut self, recording: &Recording) {
+        self.started += 1;
+        let number = self.started;
+        let written = Arc::clone(&self.written);
+        let replay = recording.0.clone();
+
+        IoTaskPool::get()
+            .spawn(async move {
+                let mut written = written.lock().unwrap_or_else(PoisonError::into_inner);
+                if *written < number {
+                    replay.save(SAVE_NAME);
+                    *written = number;
+                }
+            })
+            .detach();
+    }
+}
+
+/// Level chosen since the last recorded tick.
+#[derive(Resource, Default)]
+struct ChosenLevel(Option<usize>);
+
+/// The replay being played back, and the tick to play next.
+#[derive(Resource)]
+struct Playback {
+    replay: Replay,
+    next_frame: usize,
+    /// Whether the gameplay session playing the replay has started.
+    started: bool,
+}
+
+impl Playback {
+    fn is_playing(playback: Option<Res<Self>>) -> bool {
+        playback.is_some_and(|playback| playback.started)
+    }
+}
+
+fn start_recording(
+    mut recording: ResMut<Recording>,
+    mut chosen_level: ResMut<ChosenLevel>,
+    mut random_source: ResMut<RandomSource>,
+    starting_level: Res<StartingLevel>,
+    playback: Option<ResMut<Playback>>,
+) {
+    if let Some(mut playback) = playback {
+        playback.started = true;
+    }
+
+    // Every session starts from the same seed, so that the same input plays out the same way.
+    random_source.0 = SmallRng::seed_from_u64(RANDOM_SOURCE_SEED);
+    recording.0 = Replay {
+        seed: RANDOM_SOURCE_SEED,
+        level: starting_level.0,
+        frames: Vec::new(),
+    };
+    chosen_level.0 = None;
+}
+
+fn record_input(
+    player: Single<(&Player, &Dash, &Transform)>,
+    mut recording: ResMut<Recording>,
+    mut chosen_level: ResMut<ChosenLevel>,
+) {
+    let (player, dash, transform) = *player;
+    recording.0.frames.push(ReplayFrame {
+        movement: player.movement_direction(),
+        sneaking: player.is_sneaking(),
+        dash: dash.is_requested(),
+        position: transform.translation.truncate(),
+        rotation: transform.rotation.to_euler(EulerRot::XYZ).2,
+        chosen_level: chosen_level.0.take(),
+    });
+}
+
+fn choose_level(
+    choose: On<ChooseLevel>,
+    mut commands: Commands,
+    mut chosen_level: ResMut<ChosenLevel>,
+    playback: Option<Res<Playback>>,
+) {
+    if !Playback::is_playing(playback) {
+        chosen_level.0 = Some(choose.0);
+    }
+    commands.trigger(NewLevel(choose.0));
+}
+
+/// Saves the recording when the player dies, so it is kept even if the game is closed.
+fn save_recording_on_restart(
+    _: On<LevelRestart>,
+    recording: Res<Recording>,
+    mut saves: ResMut<RecordingSaves>,
+    playback: Option<Res<Playback>>,
+) {
+    if !Playback::is_playing(playback) {
+        saves.save(&recording);
+    }
+}
+
+fn save_recording(
+    recording: Res<Recording>,
+    mut saves: ResMut<RecordingSaves>,
+    playback: Option<Res<Playback>>,
+) {
+    if !Playback::is_playing(playback) {
+        saves.save(&recording);
+    }
+}
+
+fn play_replay(
+    _: On<PlayReplay>,
+    mut commands: Commands,
+    mut starting_level: ResMut<StartingLevel>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    let Some(replay) = Replay::load(SAVE_NAME) else {
+        warn!("There is no valid replay to play back");
+        return;
+    };
+
+    if replay.seed != RANDOM_SOURCE_SEED {
+        warn!("The replay was recorded with a different random seed and may play out differently");
+    }
+
+    starting_level.0 = replay.level;
+    commands.insert_resource(Playback {
+        replay,
+        next_frame: 0,
+        started: false,
+    });
+    next_screen.set(Screen::Gameplay);
+    next_menu.set(Menu::None);
+}
+
+/// Switches to the level chosen before the next tick, before anything else of the tick runs.
+fn play_back_chosen_level(mut commands: Commands, playback: Res<Playback>) {
+    let frame = playback.replay.frames.get(playback.next_frame);
+    if let Some(level) = frame.and_then(|frame| frame.chosen_level) {
+        commands.trigger(NewLevel(level));
+    }
+}
+
+fn play_back_input(
+    mut commands: Commands,
+    mut playback: ResMut<Playback>,
+    player: Single<(&mut Player, &mut Dash)>,
+) {
+    let Some(&frame) = playback.replay.frames.get(playback.next_frame) else {
+        info!("Replay finished");
+        commands.remove_resource::<Playback>();
+        return;
+    };
+    playback.next_frame += 1;
+
+    let (mut player, mut dash) = player.into_inner();
+    player.replay_input(frame.movement, frame.sneaking);
+    dash.replay_request(frame.dash);
+}
+
+fn stop_playback(mut commands: Commands, playback: Option<Res<Playback>>) {
+    if Playback::is_playing(playback) {
+        commands.remove_resource::<Playback>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use avian2d::prelude::*;
+    use bevy::{state::app::StatesPlugin, time::TimeUpdateStrategy};
+
+    use super::*;
+    use crate::{
+        Pause,
+        game::{
+            AllAssets, LevelNumber, animation,
+            arrows::{self, Arrow},
+            collision, dash, health, hit_reaction, movement, player, trigger_first_level,
+        },
+        input::ActionState,
+        tuning::{self, TuningFile},
+    };
+
+    const TICKS: usize = 160;
+
+    /// Input which walks, sneaks and dashes, so most of the player simulation is covered.
+    fn input() -> Vec<ReplayFrame> {
+        (0..TICKS)
+            .map(|tick| ReplayFrame {
+                movement: match tick {
+                    0..40 => Vec2::Y,
+                    40..80 => Vec2::ONE.normalize(),
+                    80..120 => Vec2::NEG_X,
+                    _ => Vec2::ZERO,
+                },
+                sneaking: (60..90).contains(&tick),
+                dash: tick == 100,
+                position: Vec2::ZERO,
+                rotation: 0.0,
+                chosen_level: None,
+            })
+            .collect()
+    }
+
+    /// Plays the input back in the first level, advancing time by `frame_delta` each frame, and
+    /// returns where the player ended up, how fast it moves and how many arrows were spawned.
+    fn play_back(frame_delta: Duration) -> (Vec3, Vec2, usize) {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            AssetPlugin::default(),
+            TransformPlugin,
+        ))
+        .init_asset::<TextureAtlasLayout>()
+        .init_asset::<Mesh>()
+        .init_asset::<ColorMaterial>()
+        .add_plugins((
+            PhysicsPlugins::default(),
+            tuning::plugin,
+            player::plugin,
+            movement::plugin,
+            dash::plugin,
+            hit_reaction::plugin,
+            arrows::plugin,
+            animation::plugin,
+            collision::plugin,
+            plugin,
+        ))
+        .configure_sets(
+            FixedUpdate,
+            (
+                AppSystems::TickTimers,
+                AppSystems::RecordInput,
+                AppSystems::Update,
+            )
+                .chain(),
+        )
+        .init_state::<Pause>()
+        .configure_sets(FixedUpdate, PausableSystems.run_if(in_state(Pause(false))))
+        .init_state::<Screen>()
+        .add_systems(OnEnter(Screen::Gameplay), trigger_first_level)
+        .insert_resource(AllAssets {
+            move_hint: default(),
+            stones: default(),
+            toast: default(),
+            arrow: default(),
+            cat: default(),
+            cat_bed: default(),
+            door: default(),
+            key: default(),
+            cat_hurt: default(),
+            goal_reached: default(),
+            steps: default(),
+            catex_fx_bold: default(),
+            dialogues: default(),
+        })
+        .insert_resource(ActionState::default())
+        .insert_resource(health::Difficulty::default())
+        .insert_resource(LevelNumber(1))
+        .insert_resource(StartingLevel(1))
+        .insert_resource(RandomSource(SmallRng::seed_from_u64(RANDOM_SOURCE_SEED)))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(frame_delta));
+
+        // The tuning file is loaded in the background, so wait for it before playing, or its
+        // values could be applied at a different tick in each run.
+        for _ in 0..1000 {
+            app.update();
+            if !app.world().resource::<Assets<TuningFile>>().is_empty() {
+                break;
+            }
+        }
+        app.update();
+
+        app.insert_resource(Playback {
+            replay: Replay {
+                seed: RANDOM_SOURCE_SEED,
+                level: 1,
+                frames: input(),
+            },
+            next_frame: 0,
+            started: false,
+        });
+        app.world_mut()
+            .resource_mut::<NextState<Screen>>()
+            .set(Screen::Gameplay);
+
+        for _ in 0..1000 {
+            if app.world().resource::<Recording>().ticks() >= TICKS {
+                break;
+            }
+            app.update();
+        }
+        assert_eq!(app.world().resource::<Recording>().ticks(), TICKS);
+
+        let world = app.world_mut();
+        let (transform, velocity) = world
+            .query_filtered::<(&Transform, &LinearVelocity), With<Player>>()
+            .single(world)
+            .unwrap();
+        let (translation, velocity) = (transform.translation, velocity.0);
+        let arrows = world
+            .query_filtered::<(), With<Arrow>>()
+            .iter(world)
+            .count();
+        (translation, velocity, arrows)
+    }
+
+    #[test]
+    fn replay_plays_out_the_same_at_any_frame_rate() {
+        // At 100 FPS a tick runs every one or two frames, at 32 FPS exactly two ticks run every
+        // frame.
+        let fast = play_back(Duration::from_millis(10));
+        let slow = play_back(Duration::from_micros(31_250));
+
+        assert_eq!(fast, slow);
+        assert!(fast.2 > 0, "no arrows were spawned");
+    }
+}
@@ -0,0 +1,246 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    game::{
+        LevelCompleted, NewLevel, StartingLevel, WAKE_UP_LEVEL, arrows::Arrow,
+        collectible::CollectibleProgress, collision::GameLayer, health::PlayerDamaged,
+        hit_reaction::HitReaction, player::Player,
+    },
+    persistence,
+    screens::Screen,
+};
+
+const TIME_BONUS: f32 = 3000.0;
+const TIME_PENALTY_PER_SECOND: f32 = 50.0;
+const COLLECTIBLE_POINTS: u32 = 500;
+/// Bonus for finishing a level without being hit, reduced by [`HIT_PENALTY`] per hit taken.
+const NO_HIT_BONUS: u32 = 900;
+const HIT_PENALTY: u32 = 300;
+const NEAR_MISS_POINTS: u32 = 100;
+const NEAR_MISS_RADIUS: f32 = 36.0;
+
+const HIGH_SCORE_COUNT: usize = 10;
+/// Index of the campaign table in [`HighScores::tables`], after the per-level tables.
+pub const CAMPAIGN_TABLE: usize = WAKE_UP_LEVEL;
+const SAVE_NAME: &str = "high_scores";
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<RunScore>()
+        .insert_resource(HighScores::load())
+        .add_systems(
+            OnEnter(Screen::Gameplay),
+            reset_run_score.before(super::trigger_first_level),
+        )
+        .add_systems(
+            FixedUpdate,
+            tick_level_time
+                .run_if(in_state(Screen::Gameplay))
+                .in_set(PausableSystems),
+        )
+        .add_observer(reset_level_score)
+        .add_observer(count_hit)
+        .add_observer(score_completed_level)
+        .add_observer(spawn_near_miss_sensor);
+}
+
+/// Score of the current run through the game.
+#[derive(Resource, Default)]
+pub struct RunScore {
+    /// The level the run started in, e.g. a later one picked in the level select.
+    starting_level: usize,
+    level_time: f32,
+    near_misses: u32,
+    hits_taken: u32,
+    /// Score of the last completion of each level, in completion order.
+    pub levels: Vec<(usize, u32)>,
+}
+
+impl RunScore {
+    pub fn total(&self) -> u32 {
+        self.levels.iter().map(|(_, score)| score).sum()
+    }
+
+    /// Whether the run played the whole campaign, from the first level to waking up.
+    pub fn is_campaign(&self) -> bool {
+        self.starting_level == 0
+            && (0..WAKE_UP_LEVEL).all(|level| self.levels.iter().any(|&(done, _)| done == level))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HighScore {
+    pub initials: String,
+    pub score: u32,
+}
+
+/// Local top scores of each level, followed by the campaign table.
+#[derive(Resource)]
+pub struct HighScores {
+    pub tables: [Vec<HighScore>; WAKE_UP_LEVEL + 1],
+}
+
+impl HighScores {
+    fn load() -> Self {
+        let mut high_scores = Self {
+            tables: Default::default(),
+        };
+
+        // Each line holds `<table> <initials> <score>`.
+        let saved = persistence::load(SAVE_NAME).unwrap_or_default();
+        for line in saved.lines() {
+            let mut parts = line.split_whitespace();
+            let (Some(table), Some(initials), Some(score)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            let (Ok(table), Ok(score)) = (table.parse(), score.parse()) else {
+                continue;
+            };
+
+            high_scores.insert(table, initials, score);
+        }
+
+        high_scores
+    }
+
+    fn save(&self) {
+        let mut contents = String::new();
+        for (table, scores) in self.tables.iter().enumerate() {
+            for high_score in scores {
+                contents += &format!("{table} {} {}\n", high_score.initials, high_score.score);
+            }
+        }
+
+        persistence::save(SAVE_NAME, &contents);
+    }
+
+    fn insert(&mut self, table: usize, initials: &str, score: u32) {
+        let Some(scores) = self.tables.get_mut(table) else {
+            return;
+        };
+
+        let index = scores.partition_point(|high_score| high_score.score >= score);
+        scores.insert(
+            index,
+            HighScore {
+                initials: initials.to_string(),
+                score,
+            },
+        );
+        scores.truncate(HIGH_SCORE_COUNT);
+    }
+
+    /// Records the scores of a finished run under the given initials. Only runs through the whole
+    /// campaign count for the campaign table.
+    pub fn submit(&mut self, initials: &str, run: &RunScore) {
+        for &(level, score) in &run.levels {
+            self.insert(level, initials, score);
+        }
+        if run.is_campaign() {
+            self.insert(CAMPAIGN_TABLE, initials, run.total());
+        }
+        self.save();
+    }
+}
+
+/// A sensor slightly bigger than the player collider, counting projectiles which pass close by.
+#[derive(Component, Default)]
+struct NearMissSensor {
+    nearby: Vec<Entity>,
+}
+
+fn reset_run_score(mut score: ResMut<RunScore>, starting_level: Res<StartingLevel>) {
+    *score = RunScore {
+        starting_level: starting_level.0,
+        ..default()
+    };
+}
+
+fn reset_level_score(_: On<NewLevel>, mut score: ResMut<RunScore>) {
+    score.level_time = 0.0;
+    score.near_misses = 0;
+    score.hits_taken = 0;
+}
+
+fn count_hit(_: On<PlayerDamaged>, mut score: ResMut<RunScore>) {
+    score.hits_taken += 1;
+}
+
+fn tick_level_time(time: Res<Time>, mut score: ResMut<RunScore>) {
+    score.level_time += time.delta_secs();
+}
+
+fn score_completed_level(
+    completed: On<LevelCompleted>,
+    mut score: ResMut<RunScore>,
+    collectibles: Res<CollectibleProgress>,
+) {
+    let time_score = (TIME_BONUS - score.level_time * TIME_PENALTY_PER_SECOND).max(0.0) as u32;
+    let level_score = time_score
+        + collectibles.collected as u32 * COLLECTIBLE_POINTS
+        + NO_HIT_BONUS.saturating_sub(score.hits_taken * HIT_PENALTY)
+        + score.near_misses * NEAR_MISS_POINTS;
+
+    // A level is played again after running out of lives in the next one, so only its last
+    // completion counts.
+    score.levels.retain(|&(level, _)| level != completed.0);
+    score.levels.push((completed.0, level_score));
+}
+
+fn spawn_near_miss_sensor(add: On<Add, Player>, mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("near miss sensor"),
+            NearMissSensor::default(),
+            Collider::circle(NEAR_MISS_RADIUS),
+            Sensor,
+            CollisionEventsEnabled,
+            CollisionLayers::new(GameLayer::Trigger, GameLayer::Hazard),
+            ChildOf(add.entity),
+        ))
+        .observe(on_projectile_near)
+        .observe(on_projectile_passed);
+}
+
+fn on_projectile_near(
+    event: On<CollisionStart>,
+    arrows: Query<(), With<Arrow>>,
+    mut sensors: Query<&mut NearMissSensor>,
+) {
+    let Ok(mut sensor) = sensors.get_mut(event.collider1) else {
+        return;
+    };
+
+    if arrows.contains(event.collider2) {
+        sensor.nearby.push(event.collider2);
+    }
+}
+
+fn on_projectile_passed(
+    event: On<CollisionEnd>,
+    mut score: ResMut<RunScore>,
+    arrows: Query<(), With<Arrow>>,
+    reaction: Single<&HitReaction, With<Player>>,
+    mut sensors: Query<&mut NearMissSensor>,
+) {
+    let Ok(mut sensor) = sensors.get_mut(event.collider1) else {
+        return;
+    };
+
+    let Some(index) = sensor
+        .nearby
+        .iter()
+        .position(|&entity| entity == event.collider2)
+    else {
+        return;
+    };
+    sensor.nearby.swap_remove(index);
+
+    // Projectiles which hit the player are despawned before leaving the sensor.
+    if arrows.contains(event.collider2) && !reaction.is_invulnerable() {
+        score.near_misses += 1;
+    }
+}
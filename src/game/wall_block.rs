@@ -4,22 +4,25 @@ use bevy::prelude::*;
 use crate::{
     game::{
         AllAssets, DestroyOnNewLevel, NewLevel,
+        camera::AddTrauma,
+        collision::{PlayerEntered, PlayerTrigger},
         environment::{ROAD_SIZE, WALL_Z},
-        guide::ChangeGuideText,
-        player::Player,
+        guide::{ChangeGuideText, GuidePriority},
     },
     screens::Screen,
 };
 
+const KEY_COLLECTED_TRAUMA: f32 = 0.4;
+
 pub fn plugin(app: &mut App) {
     app.add_observer(spawn);
 }
 
 #[derive(Component)]
-struct WallBlock;
+pub struct WallBlock;
 
 #[derive(Component)]
-struct Key;
+pub struct Key;
 
 fn spawn(
     new_level: On<NewLevel>,
@@ -62,26 +65,19 @@ fn spawn(
             Transform::from_xyz(0.0, ROAD_SIZE.y - 500.0, WALL_Z),
             Collider::rectangle(ROAD_SIZE.x, 400.0),
             RigidBody::Static,
-            Sensor,
-            CollisionEventsEnabled,
+            PlayerTrigger,
         ))
         .observe(on_player_enters_trigger);
 }
 
 fn on_player_enters_trigger(
-    event: On<CollisionStart>,
+    event: On<PlayerEntered>,
     mut commands: Commands,
     assets: Res<AllAssets>,
-    player: Single<Entity, With<Player>>,
 ) {
-    if event.collider1 != *player && event.collider2 != *player {
-        return;
-    }
-    commands.entity(event.observer()).despawn();
+    commands.entity(event.entity).despawn();
 
-    commands.trigger(ChangeGuideText(String::from(
-        "Door, how audacious, now you need to return back for key.",
-    )));
+    commands.trigger(ChangeGuideText::new("guide.door", GuidePriority::Hint));
 
     commands
         .spawn((
@@ -92,26 +88,24 @@ fn on_player_enters_trigger(
             Transform::from_xyz(0.0, 300.0, 75.0).with_scale(Vec2::splat(2.0).extend(1.0)),
             Collider::circle(64.0),
             RigidBody::Static,
-            Sensor,
-            CollisionEventsEnabled,
+            PlayerTrigger,
             Key,
         ))
         .observe(on_key_collected);
 }
 
 fn on_key_collected(
-    event: On<CollisionStart>,
+    event: On<PlayerEntered>,
     mut commands: Commands,
-    key: Single<Entity, With<Key>>,
-    player: Single<Entity, (With<Player>, Without<WallBlock>)>,
-    wall_blocks: Query<Entity, (With<WallBlock>, Without<Player>)>,
+    wall_blocks: Query<Entity, With<WallBlock>>,
 ) {
-    if event.collider1 != *player && event.collider2 != *player {
-        return;
-    }
-    commands.entity(*key).despawn();
+    commands.entity(event.entity).despawn();
+    commands.trigger(AddTrauma(KEY_COLLECTED_TRAUMA));
 
-    commands.trigger(ChangeGuideText(String::from("And now up again...")));
+    commands.trigger(ChangeGuideText::new(
+        "guide.key_collected",
+        GuidePriority::Hint,
+    ));
 
     for entity in wall_blocks {
         commands.entity(entity).despawn();
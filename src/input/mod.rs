@@ -0,0 +1,363 @@
+//! Player actions and the keys and gamepad buttons bound to them.
+//!
+//! Gameplay reads [`ActionState`] instead of raw input, so every action can be rebound in the
+//! controls menu. Custom bindings are saved through [`persistence`]. Touch screens get on-screen
+//! controls feeding the same actions.
+//!
+//! Keys are bound by their position on the keyboard, so the [`KeyLabels`] of the player's
+//! keyboard layout are learned from key presses to show the keys as they are labeled.
+
+use std::collections::{HashMap, HashSet};
+
+use bevy::{
+    input::{
+        InputSystems,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+    reflect::{DynamicEnum, DynamicVariant},
+};
+
+use crate::persistence;
+
+mod touch;
+
+const SAVE_NAME: &str = "bindings";
+const LABELS_SAVE_NAME: &str = "key_labels";
+/// How many keys can be bound to a single action.
+const BINDING_SLOTS: usize = 2;
+/// Stick tilt below which the stick is treated as centered.
+const STICK_DEADZONE: f32 = 0.2;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(touch::plugin);
+
+    app.insert_resource(InputBindings::load())
+        .insert_resource(KeyLabels::load())
+        .init_resource::<ActionState>()
+        .configure_sets(PreUpdate, ReadActions.after(InputSystems))
+        .add_systems(
+            PreUpdate,
+            (
+                (
+                    reset_action_state,
+                    read_keyboard_actions,
+                    read_gamepad_actions,
+                )
+                    .chain()
+                    .in_set(ReadActions),
+                learn_key_labels.after(InputSystems),
+            ),
+        );
+}
+
+/// Systems filling [`ActionState`]. Systems reading actions in [`PreUpdate`] should run after it.
+#[derive(SystemSet, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ReadActions;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Sneak,
+    Dash,
+    Interact,
+    Pause,
+}
+
+impl Action {
+    pub const ALL: [Self; 8] = [
+        Self::MoveUp,
+        Self::MoveDown,
+        Self::MoveLeft,
+        Self::MoveRight,
+        Self::Sneak,
+        Self::Dash,
+        Self::Interact,
+        Self::Pause,
+    ];
+
+    /// Localization key of the action name.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MoveUp => "action.move_up",
+            Self::MoveDown => "action.move_down",
+            Self::MoveLeft => "action.move_left",
+            Self::MoveRight => "action.move_right",
+            Self::Sneak => "action.sneak",
+            Self::Dash => "action.dash",
+            Self::Interact => "action.interact",
+            Self::Pause => "action.pause",
+        }
+    }
+
+    fn default_keys(self) -> [Option<KeyCode>; BINDING_SLOTS] {
+        match self {
+            Self::MoveUp => [Some(KeyCode::KeyW), Some(KeyCode::ArrowUp)],
+            Self::MoveDown => [Some(KeyCode::KeyS), Some(KeyCode::ArrowDown)],
+            Self::MoveLeft => [Some(KeyCode::KeyA), Some(KeyCode::ArrowLeft)],
+            Self::MoveRight => [Some(KeyCode::KeyD), Some(KeyCode::ArrowRight)],
+            Self::Sneak => [Some(KeyCode::ShiftLeft), Some(KeyCode::ShiftRight)],
+            Self::Dash => [Some(KeyCode::Space), None],
+            Self::Interact => [Some(KeyCode::KeyE), Some(KeyCode::Enter)],
+            Self::Pause => [Some(KeyCode::Escape), Some(KeyCode::KeyP)],
+        }
+    }
+
+    fn gamepad_buttons(self) -> &'static [GamepadButton] {
+        match self {
+            Self::MoveUp => &[GamepadButton::DPadUp],
+            Self::MoveDown => &[GamepadButton::DPadDown],
+            Self::MoveLeft => &[GamepadButton::DPadLeft],
+            Self::MoveRight => &[GamepadButton::DPadRight],
+            Self::Sneak => &[GamepadButton::LeftTrigger, GamepadButton::LeftTrigger2],
+            Self::Dash => &[GamepadButton::South],
+            Self::Interact => &[GamepadButton::West, GamepadButton::East],
+            Self::Pause => &[GamepadButton::Start],
+        }
+    }
+}
+
+/// Keys bound to each action, in the order of [`Action::ALL`].
+#[derive(Resource, Clone, PartialEq, Debug)]
+pub struct InputBindings {
+    keys: [[Option<KeyCode>; BINDING_SLOTS]; Action::ALL.len()],
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self {
+            keys: Action::ALL.map(Action::default_keys),
+        }
+    }
+}
+
+impl InputBindings {
+    pub fn keys(&self, action: Action) -> [Option<KeyCode>; BINDING_SLOTS] {
+        self.keys[action as usize]
+    }
+
+    pub fn bind(&mut self, action: Action, slot: usize, key: Option<KeyCode>) {
+        self.keys[action as usize][slot] = key;
+    }
+
+    fn load() -> Self {
+        let mut bindings = Self::default();
+
+        // Each line holds `<action index> <slot> <key>`, with `None` for an unbound slot.
+        let saved = persistence::load(SAVE_NAME).unwrap_or_default();
+        for line in saved.lines() {
+            let mut parts = line.split_whitespace();
+            let (Some(action), Some(slot), Some(key)) = (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            let (Ok(action), Ok(slot)) = (action.parse::<usize>(), slot.parse::<usize>()) else {
+                continue;
+            };
+            if action >= Action::ALL.len() || slot >= BINDING_SLOTS {
+                continue;
+            }
+
+            bindings.keys[action][slot] = parse_key(key);
+        }
+
+        bindings
+    }
+
+    pub fn save(&self) {
+        let mut contents = String::new();
+        for (action, keys) in self.keys.iter().enumerate() {
+            for (slot, key) in keys.iter().enumerate() {
+                let key = key.map_or(String::from("None"), |key| format!("{key:?}"));
+                contents += &format!("{action} {slot} {key}\n");
+            }
+        }
+
+        persistence::save(SAVE_NAME, &contents);
+    }
+}
+
+/// Reads a key saved by its name. Only keys without data can be read, so
+/// [`KeyCode::Unidentified`] keys are never bound.
+fn parse_key(name: &str) -> Option<KeyCode> {
+    KeyCode::from_reflect(&DynamicEnum::new(name, DynamicVariant::Unit))
+}
+
+/// What the keys pressed so far are labeled with in the player's keyboard layout, e.g. `Z` for
+/// [`KeyCode::KeyW`] on an AZERTY keyboard.
+#[derive(Resource, Default)]
+pub struct KeyLabels(HashMap<KeyCode, String>);
+
+impl KeyLabels {
+    /// Short name of a key, as shown to the player. Keys which weren't pressed yet are named as on
+    /// a QWERTY keyboard.
+    pub fn name(&self, key: KeyCode) -> String {
+        if let Some(label) = self.0.get(&key) {
+            return label.clone();
+        }
+
+        let name = format!("{key:?}");
+        name.strip_prefix("Key")
+            .or_else(|| name.strip_prefix("Digit"))
+            .unwrap_or(&name)
+            .to_string()
+    }
+
+    fn load() -> Self {
+        // Each line holds `<key> <label>`.
+        let saved = persistence::load(LABELS_SAVE_NAME).unwrap_or_default();
+        let labels = saved
+            .lines()
+            .filter_map(|line| {
+                let (key, label) = line.split_once(' ')?;
+                Some((parse_key(key)?, label.to_string()))
+            })
+            .collect();
+
+        Self(labels)
+    }
+
+    fn save(&self) {
+        let mut contents = String::new();
+        for (key, label) in &self.0 {
+            contents += &format!("{key:?} {label}\n");
+        }
+
+        persistence::save(LABELS_SAVE_NAME, &contents);
+    }
+}
+
+fn learn_key_labels(
+    mut keyboard: MessageReader<KeyboardInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut labels: ResMut<KeyLabels>,
+) {
+    // Modifiers change the character of a key, like Shift turning `1` into `!`.
+    let modified = keys.any_pressed([
+        KeyCode::ShiftLeft,
+        KeyCode::ShiftRight,
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::AltLeft,
+        KeyCode::AltRight,
+    ]);
+    if modified {
+        keyboard.clear();
+        return;
+    }
+
+    for input in keyboard.read() {
+        // Keys other than characters are labeled the same in every layout.
+        let Key::Character(character) = &input.logical_key else {
+            continue;
+        };
+        if matches!(input.key_code, KeyCode::Unidentified(_)) {
+            continue;
+        }
+
+        let label = character.to_uppercase();
+        if labels.0.get(&input.key_code) != Some(&label) {
+            labels.0.insert(input.key_code, label);
+            labels.save();
+        }
+    }
+}
+
+/// Actions held this frame.
+#[derive(Resource, Default)]
+pub struct ActionState {
+    pressed: HashSet<Action>,
+    previously_pressed: HashSet<Action>,
+    movement: Vec2,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.pressed(action) && !self.previously_pressed.contains(&action)
+    }
+
+    pub fn press(&mut self, action: Action) {
+        self.pressed.insert(action);
+    }
+
+    /// Adds to the movement direction of this frame.
+    pub fn add_movement(&mut self, movement: Vec2) {
+        self.movement += movement;
+    }
+
+    /// Direction to move in, with a length of at most `1.0`.
+    pub fn movement(&self) -> Vec2 {
+        self.movement.clamp_length_max(1.0)
+    }
+}
+
+/// Run condition which is true on the frame the action is pressed.
+pub fn action_just_pressed(action: Action) -> impl FnMut(Res<ActionState>) -> bool + Clone {
+    move |actions: Res<ActionState>| actions.just_pressed(action)
+}
+
+fn reset_action_state(mut actions: ResMut<ActionState>) {
+    let pressed = std::mem::take(&mut actions.pressed);
+    actions.previously_pressed = pressed;
+    actions.movement = Vec2::ZERO;
+}
+
+fn read_keyboard_actions(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    mut actions: ResMut<ActionState>,
+) {
+    for action in Action::ALL {
+        if bindings
+            .keys(action)
+            .into_iter()
+            .flatten()
+            .any(|key| keyboard.pressed(key))
+        {
+            actions.press(action);
+        }
+    }
+
+    let mut direction = Vec2::ZERO;
+    if actions.pressed(Action::MoveUp) {
+        direction.y += 1.0;
+    }
+    if actions.pressed(Action::MoveLeft) {
+        direction.x -= 1.0;
+    }
+    if actions.pressed(Action::MoveDown) {
+        direction.y -= 1.0;
+    }
+    if actions.pressed(Action::MoveRight) {
+        direction.x += 1.0;
+    }
+
+    actions.add_movement(direction.normalize_or_zero());
+}
+
+fn read_gamepad_actions(gamepads: Query<&Gamepad>, mut actions: ResMut<ActionState>) {
+    for gamepad in &gamepads {
+        for action in Action::ALL {
+            if gamepad.any_pressed(action.gamepad_buttons().iter().copied()) {
+                actions.press(action);
+            }
+        }
+
+        let stick = gamepad.left_stick();
+        let tilt = stick.length();
+        if tilt > STICK_DEADZONE {
+            // Rescale so movement starts from zero right at the edge of the deadzone.
+            let magnitude = ((tilt - STICK_DEADZONE) / (1.0 - STICK_DEADZONE)).min(1.0);
+            actions.add_movement(stick / tilt * magnitude);
+        }
+
+        actions.add_movement(gamepad.dpad().normalize_or_zero());
+    }
+}
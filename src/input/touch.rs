@@ -0,0 +1,208 @@
+//! On-screen controls for touch screens, shown once the first touch is detected.
+//!
+//! Touching the left half of the screen places a virtual joystick under the finger, which feeds
+//! the movement of [`ActionState`] like a gamepad stick.
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    input::{Action, ActionState, ReadActions, reset_action_state},
+    screens::Screen,
+};
+
+const JOYSTICK_RADIUS: f32 = 80.0;
+const KNOB_RADIUS: f32 = 32.0;
+const JOYSTICK_DEADZONE: f32 = 0.15;
+/// Where the joystick rests when not touched, from the bottom left corner of the window.
+const JOYSTICK_IDLE_OFFSET: Vec2 = Vec2::new(140.0, 200.0);
+const PAUSE_BUTTON_SIZE: f32 = 64.0;
+const CONTROL_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.15);
+const KNOB_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.4);
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<TouchControls>()
+        .add_systems(
+            PreUpdate,
+            read_touch_actions
+                .in_set(ReadActions)
+                .after(reset_action_state),
+        )
+        .add_systems(OnEnter(Screen::Gameplay), spawn_touch_controls)
+        .add_systems(
+            Update,
+            update_touch_controls.run_if(in_state(Screen::Gameplay)),
+        );
+}
+
+#[derive(Resource, Default)]
+struct TouchControls {
+    detected: bool,
+    joystick: Option<JoystickTouch>,
+    pause_requested: bool,
+}
+
+/// The finger currently holding the joystick.
+struct JoystickTouch {
+    id: u64,
+    start: Vec2,
+    /// Offset of the finger from `start`, limited to the joystick radius.
+    offset: Vec2,
+}
+
+#[derive(Component)]
+struct TouchControlsRoot;
+
+#[derive(Component)]
+struct JoystickBase;
+
+#[derive(Component)]
+struct JoystickKnob;
+
+fn read_touch_actions(
+    touches: Res<Touches>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut controls: ResMut<TouchControls>,
+    mut actions: ResMut<ActionState>,
+) {
+    if touches.any_just_pressed() {
+        controls.detected = true;
+    }
+
+    if controls
+        .joystick
+        .as_ref()
+        .is_some_and(|joystick| touches.get_pressed(joystick.id).is_none())
+    {
+        controls.joystick = None;
+    }
+
+    if controls.joystick.is_none()
+        && let Some(touch) = touches
+            .iter_just_pressed()
+            .find(|touch| touch.start_position().x < window.width() / 2.0)
+    {
+        controls.joystick = Some(JoystickTouch {
+            id: touch.id(),
+            start: touch.start_position(),
+            offset: Vec2::ZERO,
+        });
+    }
+
+    if let Some(joystick) = &mut controls.joystick
+        && let Some(touch) = touches.get_pressed(joystick.id)
+    {
+        joystick.offset = (touch.position() - joystick.start).clamp_length_max(JOYSTICK_RADIUS);
+
+        // Window coordinates grow downwards.
+        let stick = Vec2::new(joystick.offset.x, -joystick.offset.y) / JOYSTICK_RADIUS;
+        if stick.length() > JOYSTICK_DEADZONE {
+            actions.add_movement(stick);
+        }
+    }
+
+    if std::mem::take(&mut controls.pause_requested) {
+        actions.press(Action::Pause);
+    }
+}
+
+fn spawn_touch_controls(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Touch Controls"),
+            Node {
+                position_type: PositionType::Absolute,
+                width: percent(100),
+                height: percent(100),
+                ..default()
+            },
+            Visibility::Hidden,
+            Pickable::IGNORE,
+            TouchControlsRoot,
+            DespawnOnExit(Screen::Gameplay),
+            children![(
+                Name::new("Joystick"),
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: px(JOYSTICK_RADIUS * 2.0),
+                    height: px(JOYSTICK_RADIUS * 2.0),
+                    border_radius: BorderRadius::MAX,
+                    ..default()
+                },
+                BackgroundColor(CONTROL_COLOR),
+                Pickable::IGNORE,
+                JoystickBase,
+                children![(
+                    Name::new("Joystick Knob"),
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: px(KNOB_RADIUS * 2.0),
+                        height: px(KNOB_RADIUS * 2.0),
+                        border_radius: BorderRadius::MAX,
+                        ..default()
+                    },
+                    BackgroundColor(KNOB_COLOR),
+                    Pickable::IGNORE,
+                    JoystickKnob,
+                )],
+            )],
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Name::new("Touch Pause Button"),
+                    Button,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        right: Val::Vw(2.0),
+                        top: Val::Vh(10.0),
+                        width: px(PAUSE_BUTTON_SIZE),
+                        height: px(PAUSE_BUTTON_SIZE),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        border_radius: BorderRadius::MAX,
+                        ..default()
+                    },
+                    BackgroundColor(CONTROL_COLOR),
+                    children![(
+                        Text::new("II"),
+                        TextFont::from_font_size(32.0),
+                        Pickable::IGNORE,
+                    )],
+                ))
+                .observe(request_pause);
+        });
+}
+
+fn request_pause(_: On<Pointer<Click>>, mut controls: ResMut<TouchControls>) {
+    controls.pause_requested = true;
+}
+
+fn update_touch_controls(
+    controls: Res<TouchControls>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut root: Single<&mut Visibility, With<TouchControlsRoot>>,
+    mut base: Single<&mut Node, (With<JoystickBase>, Without<JoystickKnob>)>,
+    mut knob: Single<&mut Node, (With<JoystickKnob>, Without<JoystickBase>)>,
+) {
+    **root = if controls.detected {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+
+    let idle_center = Vec2::new(
+        JOYSTICK_IDLE_OFFSET.x,
+        window.height() - JOYSTICK_IDLE_OFFSET.y,
+    );
+    let (center, offset) = controls
+        .joystick
+        .as_ref()
+        .map_or((idle_center, Vec2::ZERO), |joystick| {
+            (joystick.start, joystick.offset)
+        });
+
+    base.left = px(center.x - JOYSTICK_RADIUS);
+    base.top = px(center.y - JOYSTICK_RADIUS);
+    knob.left = px(JOYSTICK_RADIUS - KNOB_RADIUS + offset.x);
+    knob.top = px(JOYSTICK_RADIUS - KNOB_RADIUS + offset.y);
+}
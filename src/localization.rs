@@ -0,0 +1,274 @@
+//! Translations of the game's texts.
+//!
+//! Each [`Language`] has a string table in `assets/localization/<code>.lang`, holding one
+//! `key = value` pair per line. Values may contain `{}` placeholders, which are filled in order.
+//! Keys missing from the current language fall back to English, and keys missing from every
+//! table are shown as they are.
+
+use std::{borrow::Cow, collections::HashMap, fmt::Display};
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    ecs::system::SystemParam,
+    prelude::*,
+    ui::UiSystems,
+};
+
+use crate::persistence;
+
+const SAVE_NAME: &str = "language";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<StringTable>()
+        .register_asset_loader(StringTableLoader)
+        .init_resource::<StringTables>()
+        .insert_resource(Language::load())
+        .add_systems(
+            PostUpdate,
+            update_localized_texts.before(UiSystems::Prepare),
+        );
+}
+
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    /// All languages, in declaration order.
+    const ALL: [Self; 2] = [Self::English, Self::German];
+
+    fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+        }
+    }
+
+    /// Name of the language in the language itself.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "Deutsch",
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        Self::ALL[(self as usize + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    pub fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    fn load() -> Self {
+        let saved = persistence::load(SAVE_NAME).unwrap_or_default();
+
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code() == saved.trim())
+            .unwrap_or_default()
+    }
+
+    pub fn save(self) {
+        persistence::save(SAVE_NAME, self.code());
+    }
+}
+
+#[derive(Asset, TypePath, Debug, Default)]
+pub struct StringTable {
+    entries: HashMap<String, String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StringTableLoaderError {
+    #[error("could not read string table: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("string table is not valid UTF-8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+    #[error("line {0}: expected `key = value`")]
+    InvalidLine(usize),
+}
+
+#[derive(TypePath)]
+struct StringTableLoader;
+
+impl AssetLoader for StringTableLoader {
+    type Asset = StringTable;
+    type Settings = ();
+    type Error = StringTableLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<StringTable, StringTableLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let mut table = StringTable::default();
+        for (index, line) in String::from_utf8(bytes)?.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(StringTableLoaderError::InvalidLine(index + 1))?;
+            table
+                .entries
+                .insert(key.trim().to_string(), value.trim().to_string());
+        }
+
+        Ok(table)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["lang"]
+    }
+}
+
+/// Handles of the string tables, in the order of [`Language::ALL`].
+#[derive(Resource)]
+struct StringTables([Handle<StringTable>; Language::ALL.len()]);
+
+impl FromWorld for StringTables {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+
+        Self(
+            Language::ALL
+                .map(|language| assets.load(format!("localization/{}.lang", language.code()))),
+        )
+    }
+}
+
+/// Looks up texts in the string table of the current [`Language`].
+#[derive(SystemParam)]
+pub struct Localizer<'w> {
+    language: Res<'w, Language>,
+    handles: Res<'w, StringTables>,
+    tables: Res<'w, Assets<StringTable>>,
+}
+
+impl Localizer<'_> {
+    /// Returns the text of the given key.
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        [*self.language, Language::English]
+            .into_iter()
+            .filter_map(|language| self.tables.get(&self.handles.0[language as usize]))
+            .find_map(|table| table.entries.get(key))
+            .map_or(key, String::as_str)
+    }
+
+    /// Returns the text of the given key with its placeholders filled with `args`.
+    pub fn format(&self, key: &str, args: &[&dyn Display]) -> String {
+        fill_placeholders(self.get(key), args)
+    }
+}
+
+fn fill_placeholders(template: &str, args: &[impl Display]) -> String {
+    let mut args = args.iter();
+    let mut text = String::new();
+
+    for (index, part) in template.split("{}").enumerate() {
+        if index > 0
+            && let Some(arg) = args.next()
+        {
+            text += &arg.to_string();
+        }
+        text += part;
+    }
+
+    text
+}
+
+/// Keeps the [`Text`] of the entity translated to the current [`Language`].
+#[derive(Component, Clone, PartialEq, Debug)]
+#[require(Text)]
+pub struct LocalizedText {
+    key: Cow<'static, str>,
+    args: Vec<LocalizedArg>,
+    verbatim: bool,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum LocalizedArg {
+    Verbatim(String),
+    Key(String),
+}
+
+impl LocalizedText {
+    pub fn new(key: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            key: key.into(),
+            args: Vec::new(),
+            verbatim: false,
+        }
+    }
+
+    /// Text shown as it is, such as numbers or player names.
+    pub fn verbatim(text: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            verbatim: true,
+            ..Self::new(text)
+        }
+    }
+
+    /// Fills the next `{}` placeholder of the text.
+    pub fn with_arg(mut self, arg: impl Display) -> Self {
+        self.args.push(LocalizedArg::Verbatim(arg.to_string()));
+        self
+    }
+
+    /// Fills the next `{}` placeholder of the text with the text of another key.
+    pub fn with_localized_arg(mut self, key: impl Into<String>) -> Self {
+        self.args.push(LocalizedArg::Key(key.into()));
+        self
+    }
+}
+
+impl From<&'static str> for LocalizedText {
+    fn from(key: &'static str) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<String> for LocalizedText {
+    fn from(key: String) -> Self {
+        Self::new(key)
+    }
+}
+
+fn update_localized_texts(
+    localizer: Localizer,
+    mut table_events: MessageReader<AssetEvent<StringTable>>,
+    mut texts: Query<(Ref<LocalizedText>, &mut Text)>,
+) {
+    let tables_changed = table_events.read().count() > 0;
+    let refresh_all = tables_changed || localizer.language.is_changed();
+
+    for (localized, mut text) in &mut texts {
+        if !refresh_all && !localized.is_changed() {
+            continue;
+        }
+
+        text.0 = if localized.verbatim {
+            localized.key.to_string()
+        } else {
+            let args: Vec<_> = localized
+                .args
+                .iter()
+                .map(|arg| match arg {
+                    LocalizedArg::Verbatim(text) => text.as_str(),
+                    LocalizedArg::Key(key) => localizer.get(key),
+                })
+                .collect();
+            fill_placeholders(localizer.get(&localized.key), &args)
+        };
+    }
+}
@@ -8,11 +8,18 @@ mod audio;
 #[cfg(feature = "dev")]
 mod dev_tools;
 mod game;
+mod input;
+mod localization;
 mod menus;
+mod persistence;
 mod screens;
 mod theme;
+mod tuning;
 
-use avian2d::PhysicsPlugins;
+use avian2d::{
+    PhysicsPlugins,
+    prelude::{Physics, PhysicsInterpolationPlugin, PhysicsTime},
+};
 use bevy::{asset::AssetMetaCheck, prelude::*};
 
 use crate::game::glitch_effect::GlitchEffect;
@@ -49,15 +56,20 @@ impl Plugin for AppPlugin {
 
         // Add other plugins.
         app.add_plugins((
-            PhysicsPlugins::default(),
+            // Bodies are moved on the fixed timestep, so their transforms are interpolated
+            // between ticks to render smoothly at any frame rate.
+            PhysicsPlugins::default().set(PhysicsInterpolationPlugin::interpolate_all()),
             asset_tracking::plugin,
             audio::plugin,
             game::plugin,
+            input::plugin,
+            localization::plugin,
             #[cfg(feature = "dev")]
             dev_tools::plugin,
             menus::plugin,
             screens::plugin,
             theme::plugin,
+            tuning::plugin,
         ));
 
         // Order new `AppSystems` variants by adding them here:
@@ -70,17 +82,32 @@ impl Plugin for AppPlugin {
             )
                 .chain(),
         );
+        // Gameplay simulation runs on the fixed timestep, so it plays out the same at any
+        // frame rate.
+        app.configure_sets(
+            FixedUpdate,
+            (
+                AppSystems::TickTimers,
+                AppSystems::RecordInput,
+                AppSystems::Update,
+            )
+                .chain(),
+        );
 
         // Set up the `Pause` state.
         app.init_state::<Pause>();
         app.configure_sets(Update, PausableSystems.run_if(in_state(Pause(false))));
+        app.configure_sets(FixedUpdate, PausableSystems.run_if(in_state(Pause(false))));
+        app.configure_sets(PostUpdate, PausableSystems.run_if(in_state(Pause(false))));
+        app.add_systems(OnEnter(Pause(true)), pause_physics);
+        app.add_systems(OnExit(Pause(true)), unpause_physics);
 
         // Spawn the main camera.
         app.add_systems(Startup, spawn_camera);
     }
 }
 
-/// High-level groupings of systems for the app in the `Update` schedule.
+/// High-level groupings of systems for the app in the `Update` and `FixedUpdate` schedules.
 /// When adding a new variant, make sure to order it in the `configure_sets`
 /// call above.
 #[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
@@ -101,6 +128,14 @@ struct Pause(pub bool);
 #[derive(SystemSet, Copy, Clone, Eq, PartialEq, Hash, Debug)]
 struct PausableSystems;
 
+fn pause_physics(mut time: ResMut<Time<Physics>>) {
+    time.pause();
+}
+
+fn unpause_physics(mut time: ResMut<Time<Physics>>) {
+    time.unpause();
+}
+
 fn spawn_camera(mut commands: Commands) {
     commands.spawn((
         Name::new("Camera"),
@@ -0,0 +1,191 @@
+//! The controls menu, where keys can be rebound to actions.
+
+use bevy::{
+    ecs::spawn::SpawnIter,
+    input::{
+        ButtonState,
+        common_conditions::input_just_pressed,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
+
+use crate::{
+    input::{Action, InputBindings, KeyLabels},
+    localization::LocalizedText,
+    menus::Menu,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Rebinding>();
+    app.add_systems(
+        OnEnter(Menu::Controls),
+        (reset_rebinding, spawn_controls_menu).chain(),
+    );
+    app.add_systems(
+        Update,
+        (
+            go_back.run_if(input_just_pressed(KeyCode::Escape).and(not(is_rebinding))),
+            capture_key,
+            update_bindings_grid.run_if(
+                resource_changed::<InputBindings>
+                    .or(resource_changed::<Rebinding>)
+                    .or(resource_changed::<KeyLabels>),
+            ),
+        )
+            .chain()
+            .run_if(in_state(Menu::Controls)),
+    );
+}
+
+/// The action and binding slot waiting for a key press.
+#[derive(Resource, Default)]
+struct Rebinding(Option<(Action, usize)>);
+
+#[derive(Component)]
+struct BindingList;
+
+fn is_rebinding(rebinding: Res<Rebinding>) -> bool {
+    rebinding.0.is_some()
+}
+
+fn reset_rebinding(mut rebinding: ResMut<Rebinding>) {
+    rebinding.0 = None;
+}
+
+fn spawn_controls_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Controls Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Controls),
+        children![
+            widget::header("controls.title"),
+            (
+                Name::new("Binding List"),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: px(6),
+                    ..default()
+                },
+                BindingList,
+            ),
+            widget::label("controls.hint"),
+            widget::button("controls.reset", reset_bindings),
+            widget::button("menu.back", go_back_on_click),
+        ],
+    ));
+}
+
+fn update_bindings_grid(
+    mut commands: Commands,
+    bindings: Res<InputBindings>,
+    labels: Res<KeyLabels>,
+    rebinding: Res<Rebinding>,
+    list: Single<Entity, With<BindingList>>,
+) {
+    let rows: Vec<_> = Action::ALL
+        .into_iter()
+        .map(|action| {
+            let [first, second] = [0, 1].map(|slot| {
+                let text = if rebinding.0 == Some((action, slot)) {
+                    LocalizedText::new("controls.press_key")
+                } else {
+                    bindings.keys(action)[slot]
+                        .map_or(LocalizedText::new("controls.unbound"), |key| {
+                            LocalizedText::verbatim(labels.name(key))
+                        })
+                };
+                widget::button_medium(text, rebind(action, slot))
+            });
+
+            (
+                Name::new("Binding Row"),
+                Node {
+                    column_gap: px(20),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                children![
+                    (
+                        widget::label(action.label()),
+                        Node {
+                            width: px(300),
+                            ..default()
+                        },
+                    ),
+                    first,
+                    second,
+                ],
+            )
+        })
+        .collect();
+
+    commands
+        .entity(*list)
+        .despawn_children()
+        .insert(Children::spawn(SpawnIter(rows.into_iter())));
+}
+
+fn rebind(action: Action, slot: usize) -> impl FnMut(On<Activate>, ResMut<Rebinding>) {
+    move |_, mut rebinding| {
+        rebinding.0 = Some((action, slot));
+    }
+}
+
+fn capture_key(
+    mut keyboard: MessageReader<KeyboardInput>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    let Some((action, slot)) = rebinding.0 else {
+        // Skip key presses made before rebinding starts, e.g. the one activating its button.
+        keyboard.clear();
+        return;
+    };
+
+    for input in keyboard.read() {
+        // Keys unknown to the platform can't be saved, so keep waiting for another key.
+        if input.state != ButtonState::Pressed || matches!(input.key_code, KeyCode::Unidentified(_))
+        {
+            continue;
+        }
+
+        match input.logical_key {
+            Key::Escape => {}
+            Key::Backspace | Key::Delete => {
+                bindings.bind(action, slot, None);
+                bindings.save();
+            }
+            _ => {
+                bindings.bind(action, slot, Some(input.key_code));
+                bindings.save();
+            }
+        }
+
+        // The captured key doesn't also move or activate the focused button, e.g. Enter
+        // activating "Reset".
+        keys.clear_just_pressed(input.key_code);
+        rebinding.0 = None;
+        return;
+    }
+}
+
+fn reset_bindings(
+    _: On<Activate>,
+    mut bindings: ResMut<InputBindings>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    *bindings = InputBindings::default();
+    bindings.save();
+    rebinding.0 = None;
+}
+
+fn go_back_on_click(_: On<Activate>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
@@ -2,7 +2,7 @@
 
 use bevy::{ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*};
 
-use crate::{audio::music, menus::Menu, theme::prelude::*};
+use crate::{audio::music, localization::LocalizedText, menus::Menu, theme::prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Credits), spawn_credits_menu);
@@ -21,11 +21,11 @@ fn spawn_credits_menu(mut commands: Commands) {
         GlobalZIndex(2),
         DespawnOnExit(Menu::Credits),
         children![
-            widget::header("Created by"),
+            widget::header("credits.created_by"),
             created_by(),
-            widget::header("Assets"),
+            widget::header("credits.assets"),
             assets(),
-            widget::button("Back", go_back_on_click),
+            widget::button("menu.back", go_back_on_click),
         ],
     ));
 }
@@ -62,7 +62,7 @@ fn grid(content: Vec<[&'static str; 2]>) -> impl Bundle {
         Children::spawn(SpawnIter(content.into_iter().flatten().enumerate().map(
             |(i, text)| {
                 (
-                    widget::label(text),
+                    widget::label(LocalizedText::verbatim(text)),
                     Node {
                         justify_self: if i.is_multiple_of(2) {
                             JustifySelf::End
@@ -77,7 +77,7 @@ fn grid(content: Vec<[&'static str; 2]>) -> impl Bundle {
     )
 }
 
-fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+fn go_back_on_click(_: On<Activate>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Main);
 }
 
@@ -0,0 +1,133 @@
+//! The level select menu, showing the collectibles found in each level.
+
+use bevy::{
+    ecs::spawn::{SpawnIter, SpawnWith},
+    input::common_conditions::input_just_pressed,
+    prelude::*,
+};
+
+use crate::{
+    asset_tracking::ResourceHandles,
+    game::{
+        StartingLevel, WAKE_UP_LEVEL, collectible::CollectibleProgress, level_data::level_data,
+        replay::ChooseLevel,
+    },
+    localization::LocalizedText,
+    menus::Menu,
+    screens::Screen,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::LevelSelect), spawn_level_select_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::LevelSelect).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_level_select_menu(mut commands: Commands, progress: Res<CollectibleProgress>) {
+    let rows: Vec<_> = (0..WAKE_UP_LEVEL)
+        .map(|level| {
+            let total = level_data(level).collectibles.len();
+            let collected = LocalizedText::new("level_select.collected")
+                .with_arg(progress.best[level])
+                .with_arg(total);
+            (level, level <= progress.reached, collected)
+        })
+        .collect();
+
+    commands.spawn((
+        widget::ui_root("Level Select Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::LevelSelect),
+        children![
+            widget::header("level_select.title"),
+            (
+                Name::new("Level List"),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: px(10),
+                    ..default()
+                },
+                Children::spawn(SpawnIter(rows.into_iter().map(
+                    |(level, reached, collected)| {
+                        (
+                            Name::new("Level Row"),
+                            Node {
+                                column_gap: px(30),
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+                                let name = LocalizedText::new("menu.level").with_arg(level + 1);
+                                // Levels the player hasn't reached yet can't be skipped to.
+                                if reached {
+                                    parent.spawn(widget::button(name, start_level(level)));
+                                    parent.spawn(widget::label(collected));
+                                } else {
+                                    parent.spawn(widget::label(name));
+                                    parent.spawn(widget::label("level_select.locked"));
+                                }
+                            })),
+                        )
+                    },
+                ))),
+            ),
+            widget::button("menu.back", go_back_on_click),
+        ],
+    ));
+}
+
+fn start_level(
+    level: usize,
+) -> impl FnMut(
+    On<Activate>,
+    Commands,
+    Res<State<Screen>>,
+    Res<ResourceHandles>,
+    ResMut<StartingLevel>,
+    ResMut<NextState<Screen>>,
+    ResMut<NextState<Menu>>,
+) {
+    move |_,
+          mut commands,
+          screen,
+          resource_handles,
+          mut starting_level,
+          mut next_screen,
+          mut next_menu| {
+        if screen.get() == &Screen::Gameplay {
+            commands.trigger(ChooseLevel(level));
+            next_menu.set(Menu::None);
+            return;
+        }
+
+        starting_level.0 = level;
+        next_screen.set(if resource_handles.is_all_done() {
+            Screen::Gameplay
+        } else {
+            Screen::Loading
+        });
+    }
+}
+
+fn go_back_on_click(
+    _: On<Activate>,
+    screen: Res<State<Screen>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    next_menu.set(if screen.get() == &Screen::Title {
+        Menu::Main
+    } else {
+        Menu::Pause
+    });
+}
+
+fn go_back(screen: Res<State<Screen>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(if screen.get() == &Screen::Title {
+        Menu::Main
+    } else {
+        Menu::Pause
+    });
+}
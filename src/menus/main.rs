@@ -2,7 +2,12 @@
 
 use bevy::prelude::*;
 
-use crate::{asset_tracking::ResourceHandles, menus::Menu, screens::Screen, theme::widget};
+use crate::{
+    asset_tracking::ResourceHandles,
+    menus::Menu,
+    screens::Screen,
+    theme::{interaction::Activate, widget},
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Main), spawn_main_menu);
@@ -15,22 +20,24 @@ fn spawn_main_menu(mut commands: Commands) {
         DespawnOnExit(Menu::Main),
         #[cfg(not(target_family = "wasm"))]
         children![
-            widget::button("Play", enter_loading_or_gameplay_screen),
-            widget::button("Settings", open_settings_menu),
-            widget::button("Credits", open_credits_menu),
-            widget::button("Exit", exit_app),
+            widget::button("menu.play", enter_loading_or_gameplay_screen),
+            widget::button("menu.levels", open_level_select_menu),
+            widget::button("menu.settings", open_settings_menu),
+            widget::button("menu.credits", open_credits_menu),
+            widget::button("menu.exit", exit_app),
         ],
         #[cfg(target_family = "wasm")]
         children![
-            widget::button("Play", enter_loading_or_gameplay_screen),
-            widget::button("Settings", open_settings_menu),
-            widget::button("Credits", open_credits_menu),
+            widget::button("menu.play", enter_loading_or_gameplay_screen),
+            widget::button("menu.levels", open_level_select_menu),
+            widget::button("menu.settings", open_settings_menu),
+            widget::button("menu.credits", open_credits_menu),
         ],
     ));
 }
 
 fn enter_loading_or_gameplay_screen(
-    _: On<Pointer<Click>>,
+    _: On<Activate>,
     resource_handles: Res<ResourceHandles>,
     mut next_screen: ResMut<NextState<Screen>>,
 ) {
@@ -41,15 +48,19 @@ fn enter_loading_or_gameplay_screen(
     }
 }
 
-fn open_settings_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+fn open_level_select_menu(_: On<Activate>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::LevelSelect);
+}
+
+fn open_settings_menu(_: On<Activate>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Settings);
 }
 
-fn open_credits_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+fn open_credits_menu(_: On<Activate>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Credits);
 }
 
 #[cfg(not(target_family = "wasm"))]
-fn exit_app(_: On<Pointer<Click>>, mut app_exit: MessageWriter<AppExit>) {
+fn exit_app(_: On<Activate>, mut app_exit: MessageWriter<AppExit>) {
     app_exit.write(AppExit::Success);
 }
@@ -1,7 +1,10 @@
 //! The game's menus and transitions between them.
 
+mod controls;
 mod credits;
+mod level_select;
 mod main;
+mod navigation;
 mod pause;
 mod settings;
 mod victory;
@@ -12,8 +15,11 @@ pub(super) fn plugin(app: &mut App) {
     app.init_state::<Menu>();
 
     app.add_plugins((
+        controls::plugin,
         credits::plugin,
+        level_select::plugin,
         main::plugin,
+        navigation::plugin,
         settings::plugin,
         pause::plugin,
         victory::plugin,
@@ -26,6 +32,9 @@ pub enum Menu {
     None,
     Main,
     Credits,
+    LevelSelect,
     Settings,
+    Controls,
     Pause,
+    Dialogue,
 }
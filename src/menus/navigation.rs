@@ -0,0 +1,168 @@
+//! Navigating menu buttons with the keyboard or a gamepad.
+//!
+//! Arrow keys and the D-pad move the [`FocusedButton`] to the closest button in that direction,
+//! and Enter or the South button activates it. Opening a menu focuses its first button.
+//!
+//! Enter and the South button are also gameplay actions, so buttons are only activated with them
+//! while a menu is open, or on the victory screen once the initials are entered.
+
+use bevy::{prelude::*, ui::UiSystems};
+
+use crate::{
+    menus::{Menu, victory::initials_submitted},
+    screens::Screen,
+    theme::prelude::*,
+};
+
+/// How much sideways distance counts against a button, compared to distance in the direction
+/// of navigation.
+const SIDEWAYS_PENALTY: f32 = 2.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LastFocusPosition>().add_systems(
+        PostUpdate,
+        (
+            focus_first_button.run_if(state_changed::<Menu>),
+            activate_focused_button.run_if(
+                not(in_state(Menu::None)).or(in_state(Screen::Victory).and(initials_submitted)),
+            ),
+            refocus_removed_button,
+            navigate_buttons,
+        )
+            .chain()
+            .after(UiSystems::Layout)
+            .before(UiSystems::PostLayout),
+    );
+}
+
+/// Where the focused button was, so focus can move to a nearby button when it disappears.
+#[derive(Resource, Default)]
+struct LastFocusPosition(Option<Vec2>);
+
+/// Visible buttons which can be focused, with their positions on the screen.
+fn focusable_buttons(
+    buttons: &Query<(Entity, &UiGlobalTransform, &InheritedVisibility), With<InteractionPalette>>,
+) -> impl Iterator<Item = (Entity, Vec2)> {
+    buttons
+        .iter()
+        .filter(|(_, _, visibility)| visibility.get())
+        .map(|(entity, transform, _)| (entity, transform.translation))
+}
+
+/// The button closest to the top left corner, so the first one in a column or row.
+fn first_button(
+    buttons: &Query<(Entity, &UiGlobalTransform, &InheritedVisibility), With<InteractionPalette>>,
+) -> Option<(Entity, Vec2)> {
+    focusable_buttons(buttons)
+        .min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+}
+
+fn focus_first_button(
+    mut focused: ResMut<FocusedButton>,
+    mut last_position: ResMut<LastFocusPosition>,
+    buttons: Query<(Entity, &UiGlobalTransform, &InheritedVisibility), With<InteractionPalette>>,
+) {
+    let first = first_button(&buttons);
+    focused.0 = first.map(|(entity, _)| entity);
+    last_position.0 = first.map(|(_, position)| position);
+}
+
+/// Moves focus to the closest button when the focused one is despawned, e.g. when a list of
+/// buttons is rebuilt.
+fn refocus_removed_button(
+    mut focused: ResMut<FocusedButton>,
+    mut last_position: ResMut<LastFocusPosition>,
+    buttons: Query<(Entity, &UiGlobalTransform, &InheritedVisibility), With<InteractionPalette>>,
+) {
+    let Some(entity) = focused.0 else {
+        return;
+    };
+    if let Ok((_, transform, visibility)) = buttons.get(entity)
+        && visibility.get()
+    {
+        last_position.0 = Some(transform.translation);
+        return;
+    }
+
+    let closest = last_position.0.and_then(|last_position| {
+        focusable_buttons(&buttons).min_by(|(_, a), (_, b)| {
+            let a = a.distance_squared(last_position);
+            let b = b.distance_squared(last_position);
+            a.total_cmp(&b)
+        })
+    });
+    focused.0 = closest.map(|(entity, _)| entity);
+    last_position.0 = closest.map(|(_, position)| position);
+}
+
+fn navigate_buttons(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut focused: ResMut<FocusedButton>,
+    mut last_position: ResMut<LastFocusPosition>,
+    buttons: Query<(Entity, &UiGlobalTransform, &InheritedVisibility), With<InteractionPalette>>,
+) {
+    // UI coordinates grow downwards.
+    let directions = [
+        (KeyCode::ArrowUp, GamepadButton::DPadUp, Vec2::NEG_Y),
+        (KeyCode::ArrowDown, GamepadButton::DPadDown, Vec2::Y),
+        (KeyCode::ArrowLeft, GamepadButton::DPadLeft, Vec2::NEG_X),
+        (KeyCode::ArrowRight, GamepadButton::DPadRight, Vec2::X),
+    ];
+    let Some(direction) = directions
+        .into_iter()
+        .find(|&(key, button, _)| {
+            keyboard.just_pressed(key) || gamepads.iter().any(|pad| pad.just_pressed(button))
+        })
+        .map(|(_, _, direction)| direction)
+    else {
+        return;
+    };
+
+    let current = focused
+        .0
+        .and_then(|entity| buttons.get(entity).ok())
+        .map(|(entity, transform, _)| (entity, transform.translation));
+    let Some((current, position)) = current else {
+        // The first key press only shows where the focus is.
+        let first = first_button(&buttons);
+        focused.0 = first.map(|(entity, _)| entity);
+        last_position.0 = first.map(|(_, position)| position);
+        return;
+    };
+
+    let next = focusable_buttons(&buttons)
+        .filter(|&(entity, _)| entity != current)
+        .filter_map(|(entity, other)| {
+            let offset = other - position;
+            let along = offset.dot(direction);
+            let sideways = offset.perp_dot(direction).abs();
+            (along > 0.0).then_some((entity, other, along + sideways * SIDEWAYS_PENALTY))
+        })
+        .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b));
+
+    if let Some((entity, position, _)) = next {
+        focused.0 = Some(entity);
+        last_position.0 = Some(position);
+    }
+}
+
+fn activate_focused_button(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    focused: Res<FocusedButton>,
+    buttons: Query<(Entity, &UiGlobalTransform, &InheritedVisibility), With<InteractionPalette>>,
+) {
+    let pressed = keyboard.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter])
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::South));
+    if !pressed {
+        return;
+    }
+
+    if let Some((entity, _, _)) = focused.0.and_then(|entity| buttons.get(entity).ok()) {
+        commands.trigger(Activate { entity });
+    }
+}
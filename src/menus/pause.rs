@@ -2,7 +2,10 @@
 
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
-use crate::{menus::Menu, theme::widget};
+use crate::{
+    menus::Menu,
+    theme::{interaction::Activate, widget},
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Pause), spawn_pause_menu);
@@ -18,23 +21,28 @@ fn spawn_pause_menu(mut commands: Commands) {
         GlobalZIndex(2),
         DespawnOnExit(Menu::Pause),
         children![
-            widget::header("Game paused"),
-            widget::button("Continue", close_menu),
-            widget::button("Settings", open_settings_menu),
+            widget::header("pause.title"),
+            widget::button("pause.continue", close_menu),
+            widget::button("menu.levels", open_level_select_menu),
+            widget::button("menu.settings", open_settings_menu),
             // widget::button("Quit to title", quit_to_title),
         ],
     ));
 }
 
-fn open_settings_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+fn open_level_select_menu(_: On<Activate>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::LevelSelect);
+}
+
+fn open_settings_menu(_: On<Activate>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Settings);
 }
 
-fn close_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+fn close_menu(_: On<Activate>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::None);
 }
 
-// fn quit_to_title(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+// fn quit_to_title(_: On<Activate>, mut next_screen: ResMut<NextState<Screen>>) {
 //     next_screen.set(Screen::Title);
 // }
 
@@ -4,7 +4,18 @@
 
 use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*};
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{
+    game::{
+        camera::{PlayerZoom, ReducedMotion},
+        ghost::ShowGhost,
+        health::Difficulty,
+        mouse_controls::MouseControls,
+    },
+    localization::{Language, LocalizedText},
+    menus::Menu,
+    screens::Screen,
+    theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
@@ -15,7 +26,16 @@ pub(super) fn plugin(app: &mut App) {
 
     app.add_systems(
         Update,
-        update_global_volume_label.run_if(in_state(Menu::Settings)),
+        (
+            update_global_volume_label,
+            update_difficulty_label,
+            update_language_label,
+            update_mouse_controls_label,
+            update_ghost_label,
+            update_reduced_motion_label,
+            update_zoom_label,
+        )
+            .run_if(in_state(Menu::Settings)),
     );
 }
 
@@ -25,9 +45,10 @@ fn spawn_settings_menu(mut commands: Commands) {
         GlobalZIndex(2),
         DespawnOnExit(Menu::Settings),
         children![
-            widget::header("Settings"),
+            widget::header("settings.title"),
             settings_grid(),
-            widget::button("Back", go_back_on_click),
+            widget::button("settings.controls", open_controls_menu),
+            widget::button("menu.back", go_back_on_click),
         ],
     ));
 }
@@ -44,13 +65,61 @@ fn settings_grid() -> impl Bundle {
         },
         children![
             (
-                widget::label("Master Volume"),
+                widget::label("settings.master_volume"),
                 Node {
                     justify_self: JustifySelf::End,
                     ..default()
                 }
             ),
             global_volume_widget(),
+            (
+                widget::label("settings.difficulty"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            difficulty_widget(),
+            (
+                widget::label("settings.language"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            language_widget(),
+            (
+                widget::label("settings.mouse_controls"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            mouse_controls_widget(),
+            (
+                widget::label("settings.ghost"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            ghost_widget(),
+            (
+                widget::label("settings.reduced_motion"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            reduced_motion_widget(),
+            (
+                widget::label("settings.zoom"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            zoom_widget(),
         ],
     )
 }
@@ -63,7 +132,7 @@ fn global_volume_widget() -> impl Bundle {
             ..default()
         },
         children![
-            widget::button_small("-", lower_global_volume),
+            widget::button_small(LocalizedText::verbatim("-"), lower_global_volume),
             (
                 Name::new("Current Volume"),
                 Node {
@@ -73,7 +142,7 @@ fn global_volume_widget() -> impl Bundle {
                 },
                 children![(widget::label(""), GlobalVolumeLabel)],
             ),
-            widget::button_small("+", raise_global_volume),
+            widget::button_small(LocalizedText::verbatim("+"), raise_global_volume),
         ],
     )
 }
@@ -81,12 +150,12 @@ fn global_volume_widget() -> impl Bundle {
 const MIN_VOLUME: f32 = 0.0;
 const MAX_VOLUME: f32 = 3.0;
 
-fn lower_global_volume(_: On<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
+fn lower_global_volume(_: On<Activate>, mut global_volume: ResMut<GlobalVolume>) {
     let linear = (global_volume.volume.to_linear() - 0.1).max(MIN_VOLUME);
     global_volume.volume = Volume::Linear(linear);
 }
 
-fn raise_global_volume(_: On<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
+fn raise_global_volume(_: On<Activate>, mut global_volume: ResMut<GlobalVolume>) {
     let linear = (global_volume.volume.to_linear() + 0.1).min(MAX_VOLUME);
     global_volume.volume = Volume::Linear(linear);
 }
@@ -97,14 +166,283 @@ struct GlobalVolumeLabel;
 
 fn update_global_volume_label(
     global_volume: Res<GlobalVolume>,
-    mut label: Single<&mut Text, With<GlobalVolumeLabel>>,
+    mut label: Single<&mut LocalizedText, With<GlobalVolumeLabel>>,
 ) {
     let percent = 100.0 * global_volume.volume.to_linear();
-    label.0 = format!("{percent:3.0}%");
+    label.set_if_neq(LocalizedText::verbatim(format!("{percent:3.0}%")));
+}
+
+fn difficulty_widget() -> impl Bundle {
+    (
+        Name::new("Difficulty Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small(LocalizedText::verbatim("-"), lower_difficulty),
+            (
+                Name::new("Current Difficulty"),
+                Node {
+                    padding: UiRect::horizontal(px(10)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), DifficultyLabel)],
+            ),
+            widget::button_small(LocalizedText::verbatim("+"), raise_difficulty),
+        ],
+    )
+}
+
+fn lower_difficulty(_: On<Activate>, mut difficulty: ResMut<Difficulty>) {
+    *difficulty = difficulty.easier();
+    difficulty.save();
+}
+
+fn raise_difficulty(_: On<Activate>, mut difficulty: ResMut<Difficulty>) {
+    *difficulty = difficulty.harder();
+    difficulty.save();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct DifficultyLabel;
+
+fn update_difficulty_label(
+    difficulty: Res<Difficulty>,
+    mut label: Single<&mut LocalizedText, With<DifficultyLabel>>,
+) {
+    label.set_if_neq(LocalizedText::new(difficulty.label()));
+}
+
+fn language_widget() -> impl Bundle {
+    (
+        Name::new("Language Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small(LocalizedText::verbatim("<"), previous_language),
+            (
+                Name::new("Current Language"),
+                Node {
+                    padding: UiRect::horizontal(px(10)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), LanguageLabel)],
+            ),
+            widget::button_small(LocalizedText::verbatim(">"), next_language),
+        ],
+    )
+}
+
+fn previous_language(_: On<Activate>, mut language: ResMut<Language>) {
+    *language = language.previous();
+    language.save();
+}
+
+fn next_language(_: On<Activate>, mut language: ResMut<Language>) {
+    *language = language.next();
+    language.save();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct LanguageLabel;
+
+fn update_language_label(
+    language: Res<Language>,
+    mut label: Single<&mut LocalizedText, With<LanguageLabel>>,
+) {
+    label.set_if_neq(LocalizedText::verbatim(language.name()));
+}
+
+fn mouse_controls_widget() -> impl Bundle {
+    (
+        Name::new("Mouse Controls Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small(LocalizedText::verbatim("<"), previous_mouse_controls),
+            (
+                Name::new("Current Mouse Controls"),
+                Node {
+                    padding: UiRect::horizontal(px(10)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), MouseControlsLabel)],
+            ),
+            widget::button_small(LocalizedText::verbatim(">"), next_mouse_controls),
+        ],
+    )
+}
+
+fn previous_mouse_controls(_: On<Activate>, mut controls: ResMut<MouseControls>) {
+    *controls = controls.previous();
+    controls.save();
+}
+
+fn next_mouse_controls(_: On<Activate>, mut controls: ResMut<MouseControls>) {
+    *controls = controls.next();
+    controls.save();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct MouseControlsLabel;
+
+fn update_mouse_controls_label(
+    controls: Res<MouseControls>,
+    mut label: Single<&mut LocalizedText, With<MouseControlsLabel>>,
+) {
+    label.set_if_neq(LocalizedText::new(controls.label()));
+}
+
+fn ghost_widget() -> impl Bundle {
+    (
+        Name::new("Ghost Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small(LocalizedText::verbatim("<"), toggle_ghost),
+            (
+                Name::new("Current Ghost"),
+                Node {
+                    padding: UiRect::horizontal(px(10)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), GhostLabel)],
+            ),
+            widget::button_small(LocalizedText::verbatim(">"), toggle_ghost),
+        ],
+    )
+}
+
+fn toggle_ghost(_: On<Activate>, mut show_ghost: ResMut<ShowGhost>) {
+    show_ghost.0 = !show_ghost.0;
+    show_ghost.save();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct GhostLabel;
+
+fn update_ghost_label(
+    show_ghost: Res<ShowGhost>,
+    mut label: Single<&mut LocalizedText, With<GhostLabel>>,
+) {
+    let key = if show_ghost.0 {
+        "settings.on"
+    } else {
+        "settings.off"
+    };
+    label.set_if_neq(LocalizedText::new(key));
+}
+
+fn reduced_motion_widget() -> impl Bundle {
+    (
+        Name::new("Reduced Motion Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small(LocalizedText::verbatim("<"), toggle_reduced_motion),
+            (
+                Name::new("Current Reduced Motion"),
+                Node {
+                    padding: UiRect::horizontal(px(10)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), ReducedMotionLabel)],
+            ),
+            widget::button_small(LocalizedText::verbatim(">"), toggle_reduced_motion),
+        ],
+    )
+}
+
+fn toggle_reduced_motion(_: On<Activate>, mut reduced_motion: ResMut<ReducedMotion>) {
+    reduced_motion.0 = !reduced_motion.0;
+    reduced_motion.save();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ReducedMotionLabel;
+
+fn update_reduced_motion_label(
+    reduced_motion: Res<ReducedMotion>,
+    mut label: Single<&mut LocalizedText, With<ReducedMotionLabel>>,
+) {
+    let key = if reduced_motion.0 {
+        "settings.on"
+    } else {
+        "settings.off"
+    };
+    label.set_if_neq(LocalizedText::new(key));
+}
+
+fn zoom_widget() -> impl Bundle {
+    (
+        Name::new("Zoom Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small(LocalizedText::verbatim("-"), zoom_out),
+            (
+                Name::new("Current Zoom"),
+                Node {
+                    padding: UiRect::horizontal(px(10)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), ZoomLabel)],
+            ),
+            widget::button_small(LocalizedText::verbatim("+"), zoom_in),
+        ],
+    )
+}
+
+fn zoom_out(_: On<Activate>, mut zoom: ResMut<PlayerZoom>) {
+    zoom.zoom_out();
+    zoom.save();
+}
+
+fn zoom_in(_: On<Activate>, mut zoom: ResMut<PlayerZoom>) {
+    zoom.zoom_in();
+    zoom.save();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ZoomLabel;
+
+fn update_zoom_label(
+    zoom: Res<PlayerZoom>,
+    mut label: Single<&mut LocalizedText, With<ZoomLabel>>,
+) {
+    let percent = 100.0 * zoom.0;
+    label.set_if_neq(LocalizedText::verbatim(format!("{percent:3.0}%")));
+}
+
+fn open_controls_menu(_: On<Activate>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Controls);
 }
 
 fn go_back_on_click(
-    _: On<Pointer<Click>>,
+    _: On<Activate>,
     screen: Res<State<Screen>>,
     mut next_menu: ResMut<NextState<Menu>>,
 ) {
@@ -1,23 +1,169 @@
-use bevy::prelude::*;
+use bevy::{
+    ecs::spawn::SpawnIter,
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
 
-use crate::{screens::Screen, theme::widget};
+use crate::{
+    game::score::{CAMPAIGN_TABLE, HighScores, RunScore},
+    localization::LocalizedText,
+    screens::Screen,
+    theme::{interaction::Activate, widget},
+};
+
+const INITIALS_LENGTH: usize = 3;
 
 pub fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Victory), spawn);
+    app.init_resource::<Initials>()
+        .add_systems(OnEnter(Screen::Victory), (reset_initials, spawn).chain())
+        .add_systems(
+            Update,
+            (type_initials, update_initials_label)
+                .chain()
+                .run_if(in_state(Screen::Victory)),
+        );
+}
+
+/// Initials typed in by the player for the high score tables.
+#[derive(Resource, Default)]
+pub(super) struct Initials {
+    text: String,
+    submitted: bool,
+}
+
+#[derive(Component)]
+struct InitialsLabel;
+
+#[derive(Component)]
+struct HighScoreTables;
+
+/// Whether the initials are entered, after which Enter no longer submits them.
+pub(super) fn initials_submitted(initials: Res<Initials>) -> bool {
+    initials.submitted
 }
 
-fn spawn(mut commands: Commands) {
+fn reset_initials(mut initials: ResMut<Initials>) {
+    *initials = Initials::default();
+}
+
+fn spawn(mut commands: Commands, score: Res<RunScore>, high_scores: Res<HighScores>) {
     commands.spawn((
         widget::ui_root("CGCG You Woke Up!"),
         GlobalZIndex(2),
         DespawnOnExit(Screen::Victory),
         children![
-            widget::header("CGCG You Woke Up!"),
-            widget::button("Restart", on_restart_click),
+            widget::header("victory.title"),
+            widget::label(LocalizedText::new("victory.score").with_arg(score.total())),
+            (widget::label(""), InitialsLabel),
+            (
+                Name::new("High Score Tables"),
+                Node {
+                    column_gap: px(40),
+                    ..default()
+                },
+                HighScoreTables,
+                high_score_columns(&high_scores),
+            ),
+            widget::button("victory.restart", on_restart_click),
         ],
     ));
 }
 
-fn on_restart_click(_: On<Pointer<Click>>, mut screen: ResMut<NextState<Screen>>) {
+fn high_score_columns(high_scores: &HighScores) -> impl Bundle {
+    let columns: Vec<_> = high_scores
+        .tables
+        .iter()
+        .enumerate()
+        .map(|(table, scores)| {
+            let title = if table == CAMPAIGN_TABLE {
+                LocalizedText::new("victory.campaign")
+            } else {
+                LocalizedText::new("menu.level").with_arg(table + 1)
+            };
+            let rows: Vec<_> = scores
+                .iter()
+                .map(|high_score| {
+                    LocalizedText::verbatim(format!("{} {}", high_score.initials, high_score.score))
+                })
+                .collect();
+
+            (title, rows)
+        })
+        .collect();
+
+    Children::spawn(SpawnIter(columns.into_iter().map(|(title, rows)| {
+        (
+            Name::new("High Score Column"),
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: px(4),
+                ..default()
+            },
+            Children::spawn((
+                Spawn(widget::label(title)),
+                SpawnIter(rows.into_iter().map(widget::label)),
+            )),
+        )
+    })))
+}
+
+fn type_initials(
+    mut commands: Commands,
+    mut keyboard: MessageReader<KeyboardInput>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut initials: ResMut<Initials>,
+    score: Res<RunScore>,
+    mut high_scores: ResMut<HighScores>,
+    tables: Single<Entity, With<HighScoreTables>>,
+) {
+    for input in keyboard.read() {
+        if initials.submitted || input.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &input.logical_key {
+            Key::Character(text) => {
+                for character in text.chars().filter(char::is_ascii_alphanumeric) {
+                    if initials.text.len() < INITIALS_LENGTH {
+                        initials.text.push(character.to_ascii_uppercase());
+                    }
+                }
+            }
+            Key::Backspace => {
+                initials.text.pop();
+            }
+            Key::Enter if !initials.text.is_empty() => {
+                initials.submitted = true;
+                high_scores.submit(&initials.text, &score);
+                // Don't let the same press activate the focused button.
+                keys.clear_just_pressed(input.key_code);
+
+                commands
+                    .entity(*tables)
+                    .despawn_children()
+                    .insert(high_score_columns(&high_scores));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn update_initials_label(
+    initials: Res<Initials>,
+    mut label: Single<&mut LocalizedText, With<InitialsLabel>>,
+) {
+    label.set_if_neq(if initials.submitted {
+        LocalizedText::new("victory.saved_as").with_arg(&initials.text)
+    } else {
+        LocalizedText::new("victory.initials_prompt")
+            .with_arg(format!("{:_<INITIALS_LENGTH$}", initials.text))
+    });
+}
+
+fn on_restart_click(_: On<Activate>, mut screen: ResMut<NextState<Screen>>) {
     screen.set(Screen::Gameplay);
 }
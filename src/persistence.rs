@@ -0,0 +1,40 @@
+//! Saving and loading of small text files holding player progress.
+//!
+//! Native builds store the files in the [`SAVE_DIR`] directory. Web builds have no
+//! file system, so nothing is kept between sessions there.
+
+#[cfg(not(target_family = "wasm"))]
+use bevy::prelude::*;
+
+#[cfg(not(target_family = "wasm"))]
+const SAVE_DIR: &str = "saves";
+
+/// Returns the contents of the save with the given name, if there is one.
+#[cfg(not(target_family = "wasm"))]
+pub fn load(name: &str) -> Option<String> {
+    std::fs::read_to_string(save_path(name)).ok()
+}
+
+/// Overwrites the save with the given name.
+#[cfg(not(target_family = "wasm"))]
+pub fn save(name: &str, contents: &str) {
+    let result =
+        std::fs::create_dir_all(SAVE_DIR).and_then(|_| std::fs::write(save_path(name), contents));
+
+    if let Err(error) = result {
+        warn!("Failed to save {name}: {error}");
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn save_path(name: &str) -> std::path::PathBuf {
+    std::path::Path::new(SAVE_DIR).join(format!("{name}.txt"))
+}
+
+#[cfg(target_family = "wasm")]
+pub fn load(_name: &str) -> Option<String> {
+    None
+}
+
+#[cfg(target_family = "wasm")]
+pub fn save(_name: &str, _contents: &str) {}
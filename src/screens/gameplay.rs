@@ -1,8 +1,13 @@
 //! The screen state for the main gameplay.
 
-use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy::prelude::*;
 
-use crate::{Pause, menus::Menu, screens::Screen};
+use crate::{
+    Pause,
+    input::{Action, action_just_pressed},
+    menus::Menu,
+    screens::Screen,
+};
 
 pub(super) fn plugin(app: &mut App) {
     // Toggle pause on key press.
@@ -12,12 +17,12 @@ pub(super) fn plugin(app: &mut App) {
             (pause, spawn_pause_overlay, open_pause_menu).run_if(
                 in_state(Screen::Gameplay)
                     .and(in_state(Menu::None))
-                    .and(input_just_pressed(KeyCode::KeyP).or(input_just_pressed(KeyCode::Escape))),
+                    .and(action_just_pressed(Action::Pause)),
             ),
             close_menu.run_if(
                 in_state(Screen::Gameplay)
-                    .and(not(in_state(Menu::None)))
-                    .and(input_just_pressed(KeyCode::KeyP)),
+                    .and(in_state(Menu::Pause))
+                    .and(action_just_pressed(Action::Pause)),
             ),
         ),
     );
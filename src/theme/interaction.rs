@@ -1,15 +1,23 @@
-use bevy::prelude::*;
+use bevy::{picking::pointer::PointerButton, prelude::*, ui::UiSystems};
 
 use crate::audio::sound_effect;
 
 pub(super) fn plugin(app: &mut App) {
+    app.add_observer(activate_on_click);
     app.add_observer(apply_interaction_palette_on_click);
     app.add_observer(apply_interaction_palette_on_over);
     app.add_observer(apply_interaction_palette_on_out);
     app.add_observer(apply_interaction_palette_on_release);
+    app.init_resource::<FocusedButton>();
+    app.add_systems(
+        PostUpdate,
+        apply_interaction_palette_on_focus
+            .run_if(resource_changed::<FocusedButton>)
+            .in_set(UiSystems::PostLayout),
+    );
 
     // app.load_resource::<InteractionAssets>();
-    app.add_observer(play_sound_effect_on_click);
+    app.add_observer(play_sound_effect_on_activate);
     app.add_observer(play_sound_effect_on_over);
 }
 
@@ -22,6 +30,31 @@ pub struct InteractionPalette {
     pub none: Color,
     pub hovered: Color,
     pub pressed: Color,
+    /// Used while the button has the keyboard or gamepad focus, see [`FocusedButton`].
+    pub focused: Color,
+}
+
+/// The button selected with the keyboard or a gamepad, if any.
+#[derive(Resource, Default, Debug)]
+pub struct FocusedButton(pub Option<Entity>);
+
+/// Triggered on a button when it is clicked, or pressed while it has the keyboard or gamepad
+/// focus. Button actions observe this rather than [`Pointer<Click>`].
+#[derive(EntityEvent, Debug)]
+pub struct Activate {
+    pub entity: Entity,
+}
+
+fn activate_on_click(
+    click: On<Pointer<Click>>,
+    mut commands: Commands,
+    buttons: Query<(), With<InteractionPalette>>,
+) {
+    if click.button == PointerButton::Primary && buttons.contains(click.event_target()) {
+        commands.trigger(Activate {
+            entity: click.event_target(),
+        });
+    }
 }
 
 fn apply_interaction_palette_on_click(
@@ -59,13 +92,37 @@ fn apply_interaction_palette_on_over(
 
 fn apply_interaction_palette_on_out(
     out: On<Pointer<Out>>,
+    focused: Res<FocusedButton>,
     mut palette_query: Query<(&InteractionPalette, &mut BackgroundColor)>,
 ) {
     let Ok((palette, mut bg)) = palette_query.get_mut(out.event_target()) else {
         return;
     };
 
-    *bg = palette.none.into();
+    *bg = if focused.0 == Some(out.event_target()) {
+        palette.focused.into()
+    } else {
+        palette.none.into()
+    };
+}
+
+fn apply_interaction_palette_on_focus(
+    focused: Res<FocusedButton>,
+    mut previously_focused: Local<Option<Entity>>,
+    mut palette_query: Query<(&InteractionPalette, &mut BackgroundColor)>,
+) {
+    if let Some(entity) = previously_focused.take()
+        && let Ok((palette, mut bg)) = palette_query.get_mut(entity)
+    {
+        *bg = palette.none.into();
+    }
+
+    if let Some(entity) = focused.0
+        && let Ok((palette, mut bg)) = palette_query.get_mut(entity)
+    {
+        *bg = palette.focused.into();
+        *previously_focused = Some(entity);
+    }
 }
 
 #[derive(Resource, Asset, Clone, Reflect)]
@@ -87,8 +144,8 @@ impl FromWorld for InteractionAssets {
     }
 }
 
-fn play_sound_effect_on_click(
-    _: On<Pointer<Click>>,
+fn play_sound_effect_on_activate(
+    _: On<Activate>,
     interaction_assets: If<Res<InteractionAssets>>,
     mut commands: Commands,
 ) {
@@ -1,4 +1,6 @@
 //! Helper functions for creating common widgets.
+//!
+//! Widget texts are looked up as localization keys, see [`LocalizedText`].
 
 use std::borrow::Cow;
 
@@ -7,7 +9,10 @@ use bevy::{
     prelude::*,
 };
 
-use crate::theme::{interaction::InteractionPalette, palette::*};
+use crate::{
+    localization::LocalizedText,
+    theme::{interaction::InteractionPalette, palette::*},
+};
 
 /// A root UI node that fills the window and centers its content.
 pub fn ui_root(name: impl Into<Cow<'static, str>>) -> impl Bundle {
@@ -29,27 +34,28 @@ pub fn ui_root(name: impl Into<Cow<'static, str>>) -> impl Bundle {
 }
 
 /// A simple header label. Bigger than [`label`].
-pub fn header(text: impl Into<String>) -> impl Bundle {
+pub fn header(text: impl Into<LocalizedText>) -> impl Bundle {
     (
         Name::new("Header"),
-        Text(text.into()),
+        text.into(),
         TextFont::from_font_size(40.0),
         TextColor(HEADER_TEXT),
     )
 }
 
 /// A simple text label.
-pub fn label(text: impl Into<String>) -> impl Bundle {
+pub fn label(text: impl Into<LocalizedText>) -> impl Bundle {
     (
         Name::new("Label"),
-        Text(text.into()),
+        text.into(),
         TextFont::from_font_size(24.0),
         TextColor(LABEL_TEXT),
     )
 }
 
-/// A large rounded button with text and an action defined as an [`Observer`].
-pub fn button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
+/// A large rounded button with text and an action defined as an [`Observer`] of
+/// [`Activate`](super::interaction::Activate).
+pub fn button<E, B, M, I>(text: impl Into<LocalizedText>, action: I) -> impl Bundle
 where
     E: EntityEvent,
     B: Bundle,
@@ -69,8 +75,31 @@ where
     )
 }
 
-/// A small square button with text and an action defined as an [`Observer`].
-pub fn button_small<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
+/// A medium rounded button with text and an action defined as an [`Observer`] of
+/// [`Activate`](super::interaction::Activate).
+pub fn button_medium<E, B, M, I>(text: impl Into<LocalizedText>, action: I) -> impl Bundle
+where
+    E: EntityEvent,
+    B: Bundle,
+    I: IntoObserverSystem<E, B, M>,
+{
+    button_base(
+        text,
+        action,
+        Node {
+            width: px(240),
+            height: px(50),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            border_radius: BorderRadius::MAX,
+            ..default()
+        },
+    )
+}
+
+/// A small square button with text and an action defined as an [`Observer`] of
+/// [`Activate`](super::interaction::Activate).
+pub fn button_small<E, B, M, I>(text: impl Into<LocalizedText>, action: I) -> impl Bundle
 where
     E: EntityEvent,
     B: Bundle,
@@ -89,9 +118,10 @@ where
     )
 }
 
-/// A simple button with text and an action defined as an [`Observer`]. The button's layout is provided by `button_bundle`.
+/// A simple button with text and an action defined as an [`Observer`] of
+/// [`Activate`](super::interaction::Activate). The button's layout is provided by `button_bundle`.
 fn button_base<E, B, M, I>(
-    text: impl Into<String>,
+    text: impl Into<LocalizedText>,
     action: I,
     button_bundle: impl Bundle,
 ) -> impl Bundle
@@ -115,10 +145,11 @@ where
                         none: BUTTON_BACKGROUND,
                         hovered: BUTTON_HOVERED_BACKGROUND,
                         pressed: BUTTON_PRESSED_BACKGROUND,
+                        focused: BUTTON_FOCUSED_BACKGROUND,
                     },
                     children![(
                         Name::new("Button Text"),
-                        Text(text),
+                        text,
                         TextFont::from_font_size(40.0),
                         TextColor(BUTTON_TEXT),
                         // Don't bubble picking events from the text up to the button.
@@ -0,0 +1,175 @@
+//! Gameplay values which can be balanced without recompiling.
+//!
+//! Each [`Tuning`] resource is a section of `assets/tuning/game.tuning`, holding one
+//! `section.field = value` pair per line. Values missing from the file keep their defaults, and
+//! changes to the file are applied while the game runs in native dev builds.
+
+use std::collections::HashMap;
+#[cfg(feature = "dev")]
+use std::fmt::Write;
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+    reflect::Struct,
+};
+
+const TUNING_PATH: &str = "tuning/game.tuning";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<TuningFile>()
+        .register_asset_loader(TuningFileLoader)
+        .init_resource::<TuningHandle>();
+}
+
+/// A resource of gameplay values, loaded from its section of the tuning file.
+///
+/// Fields can be `f32`, `u32` or `usize`.
+pub trait Tuning: Resource + Struct + Default {
+    /// Prefix of the keys of this resource in the tuning file.
+    const SECTION: &'static str;
+}
+
+/// Inserts the default values of `T` and keeps them in sync with the tuning file.
+pub fn init_tuning<T: Tuning>(app: &mut App) {
+    app.init_resource::<T>()
+        .add_systems(PreUpdate, apply_tuning::<T>);
+}
+
+/// Writes the current values of `T` in the format of the tuning file.
+#[cfg(feature = "dev")]
+pub fn format_tuning<T: Tuning>(tuning: &T) -> String {
+    let mut contents = String::new();
+    for (index, value) in tuning.iter_fields().enumerate() {
+        let (Some(name), Some(value)) = (tuning.name_at(index), format_value(value)) else {
+            continue;
+        };
+        let _ = writeln!(contents, "{}.{name} = {value}", T::SECTION);
+    }
+    contents
+}
+
+/// Returns the value as it is written in the tuning file, if it is of a supported type.
+#[cfg(feature = "dev")]
+pub fn format_value(value: &dyn PartialReflect) -> Option<String> {
+    if let Some(value) = value.try_downcast_ref::<f32>() {
+        Some(format!("{value:?}"))
+    } else if let Some(value) = value.try_downcast_ref::<u32>() {
+        Some(value.to_string())
+    } else {
+        value.try_downcast_ref::<usize>().map(ToString::to_string)
+    }
+}
+
+/// Parses `value` into the field, returning whether the field has a supported type and the value
+/// is valid for it.
+fn parse_value(field: &mut dyn PartialReflect, value: &str) -> bool {
+    if let Some(field) = field.try_downcast_mut::<f32>() {
+        value.parse().map(|value| *field = value).is_ok()
+    } else if let Some(field) = field.try_downcast_mut::<u32>() {
+        value.parse().map(|value| *field = value).is_ok()
+    } else if let Some(field) = field.try_downcast_mut::<usize>() {
+        value.parse().map(|value| *field = value).is_ok()
+    } else {
+        false
+    }
+}
+
+#[derive(Asset, TypePath, Debug, Default)]
+pub struct TuningFile {
+    entries: HashMap<String, String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TuningFileLoaderError {
+    #[error("could not read tuning file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("tuning file is not valid UTF-8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+    #[error("line {0}: expected `section.field = value`")]
+    InvalidLine(usize),
+}
+
+#[derive(TypePath)]
+struct TuningFileLoader;
+
+impl AssetLoader for TuningFileLoader {
+    type Asset = TuningFile;
+    type Settings = ();
+    type Error = TuningFileLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<TuningFile, TuningFileLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let mut file = TuningFile::default();
+        for (index, line) in String::from_utf8(bytes)?.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(TuningFileLoaderError::InvalidLine(index + 1))?;
+            file.entries
+                .insert(key.trim().to_string(), value.trim().to_string());
+        }
+
+        Ok(file)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tuning"]
+    }
+}
+
+#[derive(Resource)]
+struct TuningHandle(Handle<TuningFile>);
+
+impl FromWorld for TuningHandle {
+    fn from_world(world: &mut World) -> Self {
+        Self(world.resource::<AssetServer>().load(TUNING_PATH))
+    }
+}
+
+/// Copies the section of `T` into the resource whenever the tuning file is loaded or changed.
+fn apply_tuning<T: Tuning>(
+    mut events: MessageReader<AssetEvent<TuningFile>>,
+    handle: Res<TuningHandle>,
+    files: Res<Assets<TuningFile>>,
+    mut tuning: ResMut<T>,
+) {
+    // Every event is read, so none of them is left for the next frame.
+    let changed = events.read().fold(false, |changed, event| {
+        changed || event.is_loaded_with_dependencies(&handle.0) || event.is_modified(&handle.0)
+    });
+    if !changed {
+        return;
+    }
+    let Some(file) = files.get(&handle.0) else {
+        return;
+    };
+
+    for (key, value) in &file.entries {
+        let Some(name) = key
+            .strip_prefix(T::SECTION)
+            .and_then(|name| name.strip_prefix('.'))
+        else {
+            continue;
+        };
+
+        let Some(field) = tuning.field_mut(name) else {
+            warn!("Unknown tuning value `{key}`");
+            continue;
+        };
+        if !parse_value(field, value) {
+            warn!("Invalid tuning value `{key} = {value}`");
+        }
+    }
+}